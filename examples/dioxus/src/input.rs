@@ -1,4 +1,5 @@
 use dioxus::prelude::*;
+use eld::chart::validate_segments;
 use eld::{DutyStatus, Segment};
 
 #[component]
@@ -15,18 +16,24 @@ pub fn Input(eld_data: Signal<Vec<Segment>>) -> Element {
         let end = end_hour().parse::<f32>();
 
         if let (Ok(start), Ok(end)) = (start, end) {
-            if start >= end || start < 0.0 || end > 24.0 {
-                message.set("Invalid time range!".to_string());
-                return;
-            }
-
-            eld_data.write().push(Segment {
+            let mut log = eld_data();
+            log.push(Segment {
                 start_hour: start,
                 end_hour: end,
                 status: status(),
                 location: location(),
                 note: note(),
+                odometer: None,
             });
+            if let Err(errors) = validate_segments(&log) {
+                let new_index = log.len() - 1;
+                if let Some(error) = errors.iter().find(|error| error.index == new_index) {
+                    message.set(format!("Invalid log entry: {}", error.kind));
+                    return;
+                }
+            }
+
+            eld_data.set(log);
 
             start_hour.set("".to_string());
             end_hour.set("".to_string());
@@ -73,27 +80,27 @@ pub fn Input(eld_data: Signal<Vec<Segment>>) -> Element {
 
             div { class: "status-grid",
                 button { r#type: "button", class: "{button_class(DutyStatus::OffDuty)}", onclick: move |_| status.set(DutyStatus::OffDuty),
-                    div { class: "status-box", "OFF" }
+                    div { class: "status-box", {DutyStatus::OffDuty.abbreviation()} }
                     span { class: "status-label", "Off Duty" }
                 }
                 button { r#type: "button", class: "{button_class(DutyStatus::Sleeper)}", onclick: move |_| status.set(DutyStatus::Sleeper),
-                    div { class: "status-box", "SB" }
+                    div { class: "status-box", {DutyStatus::Sleeper.abbreviation()} }
                     span { class: "status-label", "Sleeper Berth" }
                 }
                 button { r#type: "button", class: "{button_class(DutyStatus::OnDuty)}", onclick: move |_| status.set(DutyStatus::OnDuty),
-                    div { class: "status-box", "ON" }
+                    div { class: "status-box", {DutyStatus::OnDuty.abbreviation()} }
                     span { class: "status-label", "On Duty" }
                 }
                 button { r#type: "button", class: "{button_class(DutyStatus::Driving)}", onclick: move |_| status.set(DutyStatus::Driving),
-                    div { class: "status-box", "DR" }
+                    div { class: "status-box", {DutyStatus::Driving.abbreviation()} }
                     span { class: "status-label", "Driving" }
                 }
                 // button { r#type: "button", class: "{button_class(DutyStatus::PersonalConveyance)}", onclick: move |_| status.set(DutyStatus::PersonalConveyance),
-                    // div { class: "status-box", "PC" }
+                    // div { class: "status-box", {DutyStatus::PersonalConveyance.abbreviation()} }
                     // span { class: "status-label", "Personal Conveyance" }
                 // }
                 // button { r#type: "button", class: "{button_class(DutyStatus::YardMove)}", onclick: move |_| status.set(DutyStatus::YardMove),
-                    // div { class: "status-box", "YM" }
+                    // div { class: "status-box", {DutyStatus::YardMove.abbreviation()} }
                     // span { class: "status-label", "Yard Move" }
                 // }
             }
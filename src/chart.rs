@@ -1,5 +1,14 @@
+use crate::hos::{driving_limit_reached_at, find_split_sleeper_pairs, Clocks};
+use js_sys::{Array, Date};
+use std::cell::RefCell;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
 use std::fmt;
-use web_sys::{wasm_bindgen::JsCast, window, CanvasRenderingContext2d, HtmlCanvasElement};
+use std::hash::{Hash, Hasher};
+use web_sys::{
+    wasm_bindgen::JsCast, wasm_bindgen::JsValue, window, CanvasRenderingContext2d,
+    HtmlCanvasElement,
+};
 
 /// Represents a segment of time in a driver's log.
 ///
@@ -12,13 +21,90 @@ use web_sys::{wasm_bindgen::JsCast, window, CanvasRenderingContext2d, HtmlCanvas
 /// - `status`: The duty status of the driver during this time period.
 /// - `location`: A textual description of the driver's location.
 /// - `note`: A textual note of the driver's location.
+/// - `odometer`: The odometer reading recorded when the status began, if any.
+///
+/// With the `serde` feature enabled, segments serialize with their field names, the
+/// hours as numbers, and the status as its `Display` string. `odometer` is left out
+/// when it is `None`.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Segment {
     pub start_hour: f32,
     pub end_hour: f32,
     pub status: DutyStatus,
     pub location: String,
     pub note: String,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub odometer: Option<f32>,
+}
+
+impl Segment {
+    /// Starts building a segment with an empty location and note, and no odometer reading.
+    ///
+    /// # Parameters
+    /// - `start_hour`: The starting hour of the segment (in 24-hour format).
+    /// - `end_hour`: The ending hour of the segment (in 24-hour format).
+    /// - `status`: The duty status of the driver during this time period.
+    ///
+    /// # Returns
+    /// - `SegmentBuilder`: A builder to set the optional fields on before `build`.
+    pub fn builder(start_hour: f32, end_hour: f32, status: DutyStatus) -> SegmentBuilder {
+        SegmentBuilder {
+            segment: Segment {
+                start_hour,
+                end_hour,
+                status,
+                location: String::new(),
+                note: String::new(),
+                odometer: None,
+            },
+        }
+    }
+}
+
+/// Builds a `Segment`, leaving the location and note empty unless they are set.
+///
+/// Created with `Segment::builder`:
+///
+/// ```rust
+/// use eld::{DutyStatus, Segment};
+///
+/// let segment = Segment::builder(6.0, 12.0, DutyStatus::Driving)
+///     .location("Dallas, TX")
+///     .build();
+/// assert!(segment.note.is_empty());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SegmentBuilder {
+    segment: Segment,
+}
+
+impl SegmentBuilder {
+    /// Sets the location of the segment.
+    pub fn location(mut self, location: impl Into<String>) -> Self {
+        self.segment.location = location.into();
+        self
+    }
+
+    /// Sets the note of the segment.
+    pub fn note(mut self, note: impl Into<String>) -> Self {
+        self.segment.note = note.into();
+        self
+    }
+
+    /// Sets the odometer reading recorded when the status began.
+    pub fn odometer(mut self, reading: f32) -> Self {
+        self.segment.odometer = Some(reading);
+        self
+    }
+
+    /// Returns the built segment.
+    pub fn build(self) -> Segment {
+        self.segment
+    }
 }
 
 /// Represents the duty status of a driver during a time segment.
@@ -28,7 +114,12 @@ pub struct Segment {
 /// - `Sleeper`: Resting in the sleeper berth.
 /// - `Driving`: Actively driving.
 /// - `OnDuty`: Performing other work-related activities.
+///
+/// With the `serde` feature enabled, a status serializes to the same string as its
+/// `Display` impl (e.g. `"OffDuty"`); unknown strings fail to deserialize with an
+/// error listing the expected variants.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DutyStatus {
     OffDuty,
     Sleeper,
@@ -38,6 +129,87 @@ pub enum DutyStatus {
     YardMove,
 }
 
+impl DutyStatus {
+    /// Every duty status, in the canonical order used by per-status arrays.
+    pub const ALL: [DutyStatus; 6] = [
+        DutyStatus::OffDuty,
+        DutyStatus::Sleeper,
+        DutyStatus::Driving,
+        DutyStatus::OnDuty,
+        DutyStatus::PersonalConveyance,
+        DutyStatus::YardMove,
+    ];
+
+    /// Returns the position of the status in `DutyStatus::ALL`.
+    pub(crate) fn index(&self) -> usize {
+        match self {
+            DutyStatus::OffDuty => 0,
+            DutyStatus::Sleeper => 1,
+            DutyStatus::Driving => 2,
+            DutyStatus::OnDuty => 3,
+            DutyStatus::PersonalConveyance => 4,
+            DutyStatus::YardMove => 5,
+        }
+    }
+
+    /// Returns the short label of the status, e.g. `"OFF"` or `"SB"`.
+    ///
+    /// Used for the row labels of compact layouts and the ASCII chart.
+    pub fn abbreviation(&self) -> &'static str {
+        match self {
+            DutyStatus::OffDuty => "OFF",
+            DutyStatus::Sleeper => "SB",
+            DutyStatus::Driving => "D",
+            DutyStatus::OnDuty => "ON",
+            DutyStatus::PersonalConveyance => "PC",
+            DutyStatus::YardMove => "YM",
+        }
+    }
+
+    /// Returns the FMCSA event type recording the status in an ELD output file.
+    ///
+    /// Duty status changes are event type `1`, while personal conveyance and yard
+    /// moves are recorded as event type `3`.
+    pub fn fmcsa_event_type(&self) -> u8 {
+        match self {
+            DutyStatus::PersonalConveyance | DutyStatus::YardMove => 3,
+            _ => 1,
+        }
+    }
+
+    /// Returns the FMCSA event code of the status, within its `fmcsa_event_type`.
+    ///
+    /// The duty statuses use codes `1` (Off Duty), `2` (Sleeper), `3` (Driving), and
+    /// `4` (On Duty). Personal conveyance is code `1` and yard move code `2` of event
+    /// type `3`.
+    pub fn fmcsa_code(&self) -> u8 {
+        match self {
+            DutyStatus::OffDuty | DutyStatus::PersonalConveyance => 1,
+            DutyStatus::Sleeper | DutyStatus::YardMove => 2,
+            DutyStatus::Driving => 3,
+            DutyStatus::OnDuty => 4,
+        }
+    }
+}
+
+impl TryFrom<u8> for DutyStatus {
+    type Error = String;
+
+    /// Maps an FMCSA duty status code (event type `1`) back to its status.
+    ///
+    /// Personal conveyance and yard moves share codes with other statuses under event
+    /// type `3`, so they cannot be recovered from the code alone.
+    fn try_from(code: u8) -> Result<Self, Self::Error> {
+        match code {
+            1 => Ok(DutyStatus::OffDuty),
+            2 => Ok(DutyStatus::Sleeper),
+            3 => Ok(DutyStatus::Driving),
+            4 => Ok(DutyStatus::OnDuty),
+            _ => Err(format!("Unknown FMCSA duty status code: {}", code)),
+        }
+    }
+}
+
 impl fmt::Display for DutyStatus {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let status_str = match *self {
@@ -53,65 +225,649 @@ impl fmt::Display for DutyStatus {
     }
 }
 
+/// Describes why a segment, or a pair of segments, is invalid.
+///
+/// - `Reversed`: The segment ends before it starts (`start_hour > end_hour`).
+/// - `ZeroDuration`: The segment starts and ends at the same hour.
+/// - `OutOfBounds`: The segment extends before hour 0 or past hour 24.
+/// - `Overlap`: Two segments cover some of the same time.
+/// - `OutOfOrder`: A segment starts before the one listed ahead of it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SegmentErrorKind {
+    Reversed,
+    ZeroDuration,
+    OutOfBounds,
+    Overlap,
+    OutOfOrder,
+}
+
+impl fmt::Display for SegmentErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match *self {
+            SegmentErrorKind::Reversed => "segment ends before it starts",
+            SegmentErrorKind::ZeroDuration => "segment has zero duration",
+            SegmentErrorKind::OutOfBounds => "segment is outside the 0-24 hour range",
+            SegmentErrorKind::Overlap => "segments overlap",
+            SegmentErrorKind::OutOfOrder => "segments are out of chronological order",
+        };
+
+        write!(f, "{}", message)
+    }
+}
+
+impl std::error::Error for SegmentErrorKind {}
+
+/// A problem found by `validate_segments`, with the indices of the segments involved.
+///
+/// # Fields
+/// - `kind`: What is wrong.
+/// - `index`: The index of the offending segment, or of the later one of a pair.
+/// - `other`: The index of the earlier segment of a pair, or `None` when the segment
+///   is invalid on its own.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SegmentError {
+    pub kind: SegmentErrorKind,
+    pub index: usize,
+    pub other: Option<usize>,
+}
+
+impl fmt::Display for SegmentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.other {
+            Some(other) => write!(f, "Segments {} and {}: {}", other, self.index, self.kind),
+            None => write!(f, "Segment {}: {}", self.index, self.kind),
+        }
+    }
+}
+
+impl std::error::Error for SegmentError {}
+
+/// Describes why the chart could not be drawn on its canvas.
+///
+/// - `NoWindow`: There is no browser `window`, e.g. outside the browser.
+/// - `NoDocument`: The window has no `document`.
+/// - `CanvasNotFound`: No element has the given canvas id, e.g. before first paint.
+/// - `ContextUnavailable`: The canvas has no 2D rendering context.
+/// - `CastFailed`: The element or context is not of the expected type.
+/// - `DomError`: A DOM call failed with the given message.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChartError {
+    NoWindow,
+    NoDocument,
+    CanvasNotFound(String),
+    ContextUnavailable,
+    CastFailed,
+    DomError(String),
+}
+
+impl fmt::Display for ChartError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChartError::NoWindow => write!(f, "No Window found"),
+            ChartError::NoDocument => write!(f, "No Document found"),
+            ChartError::CanvasNotFound(id) => write!(f, "Canvas with id '{}' not found", id),
+            ChartError::ContextUnavailable => write!(f, "2D context is unavailable"),
+            ChartError::CastFailed => write!(f, "Failed to cast DOM object to the expected type"),
+            ChartError::DomError(message) => write!(f, "DOM error: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for ChartError {}
+
+impl From<ChartError> for String {
+    fn from(err: ChartError) -> Self {
+        err.to_string()
+    }
+}
+
+/// Checks that a single segment describes a valid time range.
+///
+/// # Parameters
+/// - `segment`: The segment to check.
+///
+/// # Returns
+/// - `Ok(())`: If the segment has a positive duration within the day.
+/// - `Err(SegmentErrorKind)`: The first problem found with the segment.
+pub fn validate_segment(segment: &Segment) -> Result<(), SegmentErrorKind> {
+    if segment.start_hour > segment.end_hour {
+        return Err(SegmentErrorKind::Reversed);
+    }
+    if segment.start_hour == segment.end_hour {
+        return Err(SegmentErrorKind::ZeroDuration);
+    }
+    if segment.start_hour < 0.0 || segment.end_hour > 24.0 {
+        return Err(SegmentErrorKind::OutOfBounds);
+    }
+    Ok(())
+}
+
+/// Checks a whole log for invalid segments, overlaps, and segments out of order.
+///
+/// Each segment is first checked on its own with `validate_segment`. Segments that pass
+/// are then compared with every earlier valid segment for overlaps, and with the
+/// segment listed just before them for chronological order. Segments that only touch
+/// at a boundary do not overlap.
+///
+/// # Parameters
+/// - `segments`: A slice of `Segment` structs representing the driver's log.
+///
+/// # Returns
+/// - `Ok(())`: If the log is valid.
+/// - `Err(Vec<SegmentError>)`: Every problem found, ordered by the later index involved.
+pub fn validate_segments(segments: &[Segment]) -> Result<(), Vec<SegmentError>> {
+    let mut errors = Vec::new();
+    let mut valid = Vec::new();
+
+    for (index, segment) in segments.iter().enumerate() {
+        if let Err(kind) = validate_segment(segment) {
+            errors.push(SegmentError {
+                kind,
+                index,
+                other: None,
+            });
+            continue;
+        }
+
+        for &other in &valid {
+            let earlier: &Segment = &segments[other];
+            if segment.start_hour < earlier.end_hour && earlier.start_hour < segment.end_hour {
+                errors.push(SegmentError {
+                    kind: SegmentErrorKind::Overlap,
+                    index,
+                    other: Some(other),
+                });
+            }
+        }
+
+        if index > 0 && segment.start_hour < segments[index - 1].start_hour {
+            errors.push(SegmentError {
+                kind: SegmentErrorKind::OutOfOrder,
+                index,
+                other: Some(index - 1),
+            });
+        }
+        valid.push(index);
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// A labeled marker placed at a specific point of the chart.
+///
+/// Annotations highlight events that happen at an instant rather than over a
+/// period, such as a DOT inspection during an On Duty period.
+///
+/// # Fields
+/// - `hour`: The time of the event (in 24-hour format).
+/// - `status`: The duty status row the marker is placed in.
+/// - `label`: The text drawn next to the marker.
+/// - `color`: The color of the marker and its label.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Annotation {
+    pub hour: f32,
+    pub status: DutyStatus,
+    pub label: String,
+    pub color: String,
+}
+
+/// Controls which vertical grid lines are drawn on the chart.
+///
+/// - `Hourly`: A vertical line at every hour (the default).
+/// - `TransitionsOnly`: Vertical lines only where a segment starts or ends.
+/// - `None`: No vertical lines; only the horizontal status rows are drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+pub enum GridMode {
+    #[default]
+    Hourly,
+    TransitionsOnly,
+    None,
+}
+
+/// Controls how duty status segments are visualized.
+///
+/// - `GridLines`: A line in the row of each segment's status (the default).
+/// - `Ribbon`: A band spanning the full grid height, colored by status.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+pub enum ChartStyle {
+    #[default]
+    GridLines,
+    Ribbon,
+}
+
+/// Controls how each segment is drawn in `ChartStyle::GridLines` mode.
+///
+/// - `Line`: A thin line through the middle of the status row (the default).
+/// - `Pill`: A rounded bar, `ChartProps::pill_height` pixels tall, filled in the status color.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+pub enum SegmentStyle {
+    #[default]
+    Line,
+    Pill,
+}
+
+/// Controls how segments extending past `ChartProps::hours_span` are drawn.
+///
+/// - `Clip`: Cut the segment at the right edge of the chart (the default).
+/// - `Wrap`: Continue the overflowing part from the start of the chart.
+/// - `Extend`: Grow the time axis so the whole segment fits.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+pub enum OverflowMode {
+    #[default]
+    Clip,
+    Wrap,
+    Extend,
+}
+
+/// Positions the chart horizontally when the canvas is wider than the chart.
+///
+/// - `Left`: Draw the chart against the left edge (the default).
+/// - `Center`: Leave the same space on both sides.
+/// - `Right`: Draw the chart against the right edge.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+pub enum Align {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
 /// Configuration properties for rendering the ELD chart.
 ///
 /// This struct defines various attributes that control the appearance of the
 /// chart, including its size, colors, and font styles.
+///
+/// `ChartProps` implements `Default` with the same values the framework components
+/// use, so callers drawing without a component only need to set the fields they change:
+///
+/// ```
+/// use eld::chart::ChartProps;
+///
+/// let props = ChartProps {
+///     width: 1200,
+///     ..Default::default()
+/// };
+/// assert_eq!(props.height, 300);
+/// ```
+///
+/// With the `serde` feature enabled, `ChartProps` can be deserialized from a theme
+/// file (e.g. JSON fetched at runtime). Every field is optional and falls back to
+/// its `Default` value, so partial configs only need to list the overrides.
 #[derive(PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize), serde(default))]
 pub struct ChartProps {
-    /// The width of the chart in pixels.
+    /// The width of the chart in CSS pixels.
     pub width: u32,
 
-    /// The height of the chart in pixels.
+    /// The height of the chart in CSS pixels.
     pub height: u32,
 
     /// The background color of the chart.
     ///
     /// This defines the overall canvas color behind the grid and segments.
-    pub background_color: &'static str,
+    pub background_color: String,
 
     /// The color of the grid lines.
     ///
     /// Grid lines are drawn to divide the chart into time slots and duty status sections.
-    pub grid_color: &'static str,
+    pub grid_color: String,
 
     /// The font used for text labels.
     ///
     /// This applies to hour labels and duty status descriptions.
-    pub font: &'static str,
+    pub font: String,
 
     /// The color of the text labels.
     ///
     /// Labels include hours along the x-axis and duty status names along the y-axis.
-    pub label_color: &'static str,
+    pub label_color: String,
 
     /// The color representing the "Off Duty" status.
     ///
     /// This color is used to draw segments where the driver is off duty.
-    pub off_duty_color: &'static str,
+    pub off_duty_color: String,
 
     /// The color representing the "Sleeper" status.
     ///
     /// This color is used to draw segments where the driver is in the sleeper berth.
-    pub sleeper_color: &'static str,
+    pub sleeper_color: String,
 
     /// The color representing the "Driving" status.
     ///
     /// This color is used to draw segments where the driver is actively driving.
-    pub driving_color: &'static str,
+    pub driving_color: String,
 
     /// The color representing the "On Duty" status.
     ///
     /// This color is used to draw segments where the driver is performing
     /// non-driving work-related activities.
-    pub on_duty_color: &'static str,
+    pub on_duty_color: String,
+
+    /// The color representing the "Personal Conveyance" status.
+    ///
+    /// Personal conveyance is off-duty use of the vehicle; it is drawn as a dashed line
+    /// in the Off Duty row.
+    pub personal_conveyance_color: String,
+
+    /// The color representing the "Yard Move" status.
+    ///
+    /// Yard moves are on-duty vehicle movements within a yard or terminal; they are drawn
+    /// as a dashed line in the On Duty row.
+    pub yard_move_color: String,
+
+    /// Which vertical grid lines are drawn.
+    ///
+    /// Horizontal row lines and hour labels are always drawn regardless of the mode.
+    pub grid_mode: GridMode,
+
+    /// Whether the status rows are drawn in reverse order.
+    ///
+    /// When `true`, On Duty is the top row and Off Duty the bottom row, matching
+    /// forms that list the statuses bottom-up.
+    pub flip_y: bool,
+
+    /// Labeled markers drawn on top of the segments.
+    ///
+    /// Annotations are log data rather than theme settings, so they are skipped
+    /// when deserializing `ChartProps`.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub annotations: Vec<Annotation>,
+
+    /// Text drawn at the bottom-right corner of the canvas in a small gray font.
+    ///
+    /// Typically used on printed reports to record when the chart was generated;
+    /// see `generated_footer`. `None` draws no footer.
+    pub footer: Option<String>,
+
+    /// The radius, in pixels, of the chart's rounded corners.
+    ///
+    /// When greater than `0.0`, all drawing is clipped to a rounded rectangle. Pair it
+    /// with a matching CSS `border-radius` on the canvas. Defaults to `0.0` (square).
+    pub corner_radius: f64,
+
+    /// How the duty status segments are visualized.
+    pub style: ChartStyle,
+
+    /// Whether all grid and segment coordinates are rounded to whole pixels.
+    ///
+    /// Produces crisp, antialias-free lines for pixel-perfect print exports.
+    pub integer_layout: bool,
+
+    /// Whether split-sleeper rest pairs are linked with a bracket below the grid.
+    ///
+    /// Pairs are found with `hos::find_split_sleeper_pairs`.
+    pub split_sleeper_brackets: bool,
+
+    /// The horizontal range, in canvas pixels, that is currently visible.
+    ///
+    /// When set, segments whose drawn extent lies entirely outside `(start, end)` are
+    /// skipped. This speeds up dense multi-day logs rendered in a scroll container.
+    /// `None` draws every segment.
+    pub visible_range: Option<(f64, f64)>,
+
+    /// The number of hours covered by the time axis.
+    ///
+    /// Defaults to `24.0` (one day).
+    pub hours_span: f32,
+
+    /// How segments running past `hours_span` are drawn.
+    pub on_overflow: OverflowMode,
+
+    /// Relative heights of the Off Duty, Sleeper, Driving, and On Duty rows.
+    ///
+    /// The available height is split proportionally, so `[1.0, 1.0, 2.0, 1.0]` makes
+    /// the Driving row twice as tall as the others. `None` gives every row the same height.
+    pub row_heights: Option<[f64; 4]>,
+
+    /// A vertical background gradient, as `(top_color, bottom_color)`.
+    ///
+    /// When set, the canvas is filled with the gradient before the grid is drawn,
+    /// covering the flat `background_color`. `None` keeps the flat background.
+    pub background_gradient: Option<(String, String)>,
+
+    /// The night hours, as `(start, end)`, tinted behind the segments.
+    ///
+    /// A window whose start is after its end wraps past midnight, e.g. `(22.0, 6.0)`.
+    /// `None` draws no tint.
+    pub night_window: Option<(f32, f32)>,
+
+    /// The translucent color used to tint the `night_window`.
+    pub night_tint_color: String,
+
+    /// Whether a summary panel is drawn to the right of the grid.
+    ///
+    /// The panel lists a color swatch, label, and total hours for each duty status,
+    /// combining a legend and a totals column. The time axis narrows to make room.
+    pub side_panel: bool,
+
+    /// How each segment is drawn within its status row.
+    ///
+    /// Only applies to `ChartStyle::GridLines`; ribbons always span the full grid height.
+    pub segment_style: SegmentStyle,
+
+    /// The height, in pixels, of each bar in `SegmentStyle::Pill` mode.
+    pub pill_height: f64,
+
+    /// Whether driving beyond the 11-hour limit is drawn in `overage_color`.
+    pub highlight_overage: bool,
+
+    /// The color of driving time past the 11-hour limit.
+    pub overage_color: String,
+
+    /// The minimum drawn width, in pixels, of each segment.
+    ///
+    /// Narrower segments are widened around their center so very short periods stay
+    /// visible and clickable; the segment data is unchanged. `0.0` draws true widths.
+    pub min_segment_px: f64,
+
+    /// Whether the row labels use abbreviations ("OFF", "SB", "D", "ON") instead of
+    /// the full status names, for compact layouts.
+    pub compact_labels: bool,
+
+    /// The `id` of the `<canvas>` element the chart is drawn into.
+    ///
+    /// Give each chart on a page its own id; the grid cache is tracked per canvas.
+    pub canvas_id: String,
+
+    /// Whether the legend in the side panel only lists statuses present in the log.
+    pub legend_active_only: bool,
+
+    /// The smallest width, in pixels, a responsive chart may shrink to.
+    ///
+    /// When `min_width` or `max_width` is set, the components size the canvas to its
+    /// container, clamped to these bounds (see `effective_width`). When both are `None`
+    /// the canvas keeps the fixed `width`.
+    pub min_width: Option<u32>,
+
+    /// The largest width, in pixels, a responsive chart may grow to.
+    ///
+    /// `draw_chart` never draws the chart wider than this; on a wider canvas the chart
+    /// is placed according to `align`.
+    pub max_width: Option<u32>,
+
+    /// The status assumed from the end of the last segment until hour 24.
+    ///
+    /// Logs that stop early are filled with this status (see `fill_end_of_day`). Use
+    /// `None` to leave the rest of the day blank, e.g. for a live log drawn with
+    /// `now_hour`.
+    pub end_of_day_status: Option<DutyStatus>,
+
+    /// The dash pattern of the second driver's lines in `draw_team`, as alternating
+    /// dash and gap lengths in pixels.
+    pub driver_b_dash: Vec<f64>,
+
+    /// A border stroked around each filled segment, as `(color, line_width)`.
+    ///
+    /// Only applies to `SegmentStyle::Pill` and `ChartStyle::Ribbon`, where it separates
+    /// adjacent segments of similar colors.
+    pub segment_border: Option<(String, f64)>,
+
+    /// The first hour shown on the time axis, to zoom into part of the day.
+    ///
+    /// Segments partly before it are clipped at the left edge of the grid.
+    pub start_hour: f32,
+
+    /// The last hour shown on the time axis, to zoom into part of the day.
+    ///
+    /// Segments partly after it are clipped at the right edge of the grid. At `24.0`
+    /// the axis ends at `hours_span`, so longer and overflowing axes keep working.
+    pub end_hour: f32,
+
+    /// Whether the odometer reading of each status change is drawn below the grid.
+    ///
+    /// Each reading is written under a short tick at the start of its segment; segments
+    /// without an `odometer` reading are skipped.
+    pub show_odometer: bool,
+
+    /// Where the chart is placed on a canvas wider than `max_width`.
+    ///
+    /// Defaults to `Align::Left`.
+    pub align: Align,
+
+    /// The current hour of the day, marked with a vertical line labeled "NOW".
+    ///
+    /// Set it for a live, in-progress log, e.g. `Some(13.5)` for 1:30 PM. Defaults to `None`,
+    /// which draws no marker.
+    pub now_hour: Option<f32>,
+
+    /// The color of the `now_hour` marker and its label.
+    pub now_color: String,
+}
+
+impl Default for ChartProps {
+    /// Returns the same defaults the framework components use when a prop is omitted.
+    fn default() -> Self {
+        Self {
+            width: 900,
+            height: 300,
+            background_color: "#FFFFFF".to_string(),
+            grid_color: "#CCCCCC".to_string(),
+            font: "bold 14px Arial".to_string(),
+            label_color: "#444444".to_string(),
+            off_duty_color: "#8E8E8E".to_string(),
+            sleeper_color: "black".to_string(),
+            driving_color: "green".to_string(),
+            on_duty_color: "orange".to_string(),
+            personal_conveyance_color: "steelblue".to_string(),
+            yard_move_color: "goldenrod".to_string(),
+            grid_mode: GridMode::Hourly,
+            flip_y: false,
+            annotations: Vec::new(),
+            footer: None,
+            corner_radius: 0.0,
+            style: ChartStyle::GridLines,
+            integer_layout: false,
+            split_sleeper_brackets: false,
+            visible_range: None,
+            hours_span: 24.0,
+            on_overflow: OverflowMode::Clip,
+            row_heights: None,
+            background_gradient: None,
+            night_window: None,
+            night_tint_color: "rgba(25, 25, 112, 0.1)".to_string(),
+            side_panel: false,
+            segment_style: SegmentStyle::Line,
+            pill_height: 12.0,
+            highlight_overage: false,
+            overage_color: "red".to_string(),
+            min_segment_px: 0.0,
+            compact_labels: false,
+            canvas_id: "eld-canvas".to_string(),
+            legend_active_only: false,
+            min_width: None,
+            max_width: None,
+            end_of_day_status: Some(DutyStatus::OffDuty),
+            driver_b_dash: vec![6.0, 4.0],
+            segment_border: None,
+            start_hour: 0.0,
+            end_hour: 24.0,
+            show_odometer: false,
+            align: Align::Left,
+            now_hour: None,
+            now_color: "#E53935".to_string(),
+        }
+    }
+}
+
+impl ChartProps {
+    /// Returns the color a duty status is drawn with.
+    ///
+    /// # Parameters
+    /// - `status`: The duty status to look up.
+    ///
+    /// # Returns
+    /// - `&str`: The matching status color field, e.g. `driving_color` for
+    ///   `DutyStatus::Driving`.
+    pub fn color_for(&self, status: &DutyStatus) -> &str {
+        match status {
+            DutyStatus::OffDuty => &self.off_duty_color,
+            DutyStatus::Sleeper => &self.sleeper_color,
+            DutyStatus::Driving => &self.driving_color,
+            DutyStatus::OnDuty => &self.on_duty_color,
+            DutyStatus::PersonalConveyance => &self.personal_conveyance_color,
+            DutyStatus::YardMove => &self.yard_move_color,
+        }
+    }
+
+    /// Returns the light theme, which is the same as `ChartProps::default()`.
+    ///
+    /// # Returns
+    /// - `ChartProps`: Dark lines and labels on a white background.
+    pub fn light() -> Self {
+        Self::default()
+    }
+
+    /// Returns a dark theme for low-light dashboards.
+    ///
+    /// The background is near-black, the grid and labels are light, and the status
+    /// colors are brightened to stay readable. Everything else, including the size,
+    /// keeps its default and can be overridden with struct-update syntax.
+    ///
+    /// # Returns
+    /// - `ChartProps`: Light lines and labels on a near-black background.
+    pub fn dark() -> Self {
+        Self {
+            background_color: "#121212".to_string(),
+            grid_color: "#5A5A5A".to_string(),
+            label_color: "#E0E0E0".to_string(),
+            off_duty_color: "#BDBDBD".to_string(),
+            sleeper_color: "#B39DDB".to_string(),
+            driving_color: "#69F0AE".to_string(),
+            on_duty_color: "#FFAB40".to_string(),
+            personal_conveyance_color: "#4FC3F7".to_string(),
+            yard_move_color: "#FFE57F".to_string(),
+            night_tint_color: "rgba(255, 255, 255, 0.06)".to_string(),
+            overage_color: "#FF5252".to_string(),
+            now_color: "#FF8A80".to_string(),
+            ..Self::default()
+        }
+    }
 }
 
 /// Renders the ELD chart using the given segments and chart properties.
 ///
 /// This function first retrieves the canvas and drawing context, then checks if
-/// the grid has already been drawn. If not, it draws the grid before rendering
-/// the duty status segments.
+/// the grid has already been drawn with the same canvas size and grid settings.
+/// If not, it draws the grid before rendering the duty status segments. With
+/// `GridMode::TransitionsOnly` the grid depends on the segments, so it is redrawn
+/// on every call.
+///
+/// Invalid segments (see `validate_segment`) are skipped with a warning naming the
+/// problem, so a single bad entry does not garble the chart. A log that stops before
+/// hour 24 is completed with `end_of_day_status`.
+///
+/// The canvas is shown at `width` by `height` CSS pixels, while its backing store is
+/// scaled by the device pixel ratio so lines and text stay sharp on high-density
+/// displays. A change of size or pixel ratio redraws the grid.
 ///
 /// # Parameters
 /// - `segments`: A slice of `Segment` structs representing the driver's log.
@@ -119,258 +875,4054 @@ pub struct ChartProps {
 ///
 /// # Returns
 /// - `Ok(&ChartProps)`: If the chart was successfully drawn.
-/// - `Err(String)`: If an error occurred (e.g., unable to find the canvas).
+/// - `Err(ChartError)`: If an error occurred (e.g., unable to find the canvas).
 pub fn draw_chart<'a>(
     segments: &'a [Segment],
     props: &'a ChartProps,
-) -> Result<&'a ChartProps, String> {
-    let canvas = get_canvas("eld-canvas")?;
+) -> Result<&'a ChartProps, ChartError> {
+    let canvas = get_canvas(&props.canvas_id)?;
     let context = get_canvas_context(&canvas)?;
 
-    let (width, height) = (canvas.width() as f64, canvas.height() as f64);
-
-    if grid_already_drawn()? {
-        draw_segments(&context, segments, width, height, props);
-        return Ok(props);
+    let (canvas_width, height) = (props.width as f64, props.height as f64);
+    let width = content_width(canvas_width, props);
+    let pixel_ratio = device_pixel_ratio();
+    if size_canvas(&canvas, props, pixel_ratio) {
+        forget_grid(&props.canvas_id);
     }
 
-    draw_grid(&context, width, height, props);
-    draw_segments(&context, segments, width, height, props);
+    let (segments, layout) = lay_out(sanitize_segments(segments), props);
+    let fingerprint = grid_fingerprint(canvas_width, height, pixel_ratio, &layout);
+    let grid_cached = grid_already_drawn(&props.canvas_id, fingerprint);
 
-    mark_grid_as_drawn()?;
-    Ok(props)
-}
+    context.save();
+    scale_context(&context, pixel_ratio);
+    if width < canvas_width {
+        if !grid_cached {
+            context.clear_rect(0.0, 0.0, canvas_width, height);
+        }
+        context
+            .translate(align_offset(canvas_width, props), 0.0)
+            .unwrap_or_else(|_| log::warn!("Failed to align the chart"));
+    }
+    if layout.corner_radius > 0.0 {
+        clip_rounded_corners(&context, width, height, layout.corner_radius);
+    }
 
-pub fn clear_chart() -> Result<(), String> {
-    let canvas = get_canvas("eld-canvas")?;
-    let context = get_canvas_context(&canvas)?;
-    context.clear_rect(0.0, 0.0, canvas.width() as f64, canvas.height() as f64);
-    let document = window()
-        .ok_or("No Window found".to_string())?
-        .document()
-        .ok_or("No Document found".to_string())?;
-    if let Some(existing_grid) = document.get_element_by_id("grid-drawn") {
-        existing_grid
-            .set_attribute("data-drawn", "falase")
-            .map_err(|_| "Failed to set attribute".to_string())?;
+    if !grid_cached || layout.grid_mode == GridMode::TransitionsOnly {
+        draw_grid(&context, &segments, width, height, &layout);
     }
-    Ok(())
+    draw_layers(&context, &segments, width, height, &layout);
+    context.restore();
+
+    if !grid_cached {
+        mark_grid_as_drawn(&props.canvas_id, fingerprint);
+    }
+    Ok(props)
 }
 
-/// Retrieves the 2D rendering context for a given HTML canvas.
+/// Redraws the chart after the log changed, repainting only the parts that differ.
 ///
-/// # Parameters
-/// - `canvas`: A reference to the HTML canvas element.
+/// The hour ranges where `previous` and `segments` differ (see `diff_logs`) are
+/// cleared and drawn again, grid included, while the rest of the canvas is left as
+/// it is. This keeps updates cheap on large logs where a single segment changes.
 ///
-/// # Returns
-/// - `Ok(HtmlCanvasElement)`: The HTML canvas element.
-/// - `Err(String)`: If the context cannot be obtained or cast.
-fn get_canvas(id: &str) -> Result<HtmlCanvasElement, String> {
-    window()
-        .ok_or("No Window found".to_string())?
-        .document()
-        .ok_or("No Document found".to_string())?
-        .get_element_by_id(id)
-        .ok_or_else(|| format!("Canvas with id '{}' not found", id))?
-        .dyn_into::<HtmlCanvasElement>()
-        .map_err(|_| "Failed to cast element to Canvas".to_string())
-}
-
-/// Retrieves the 2D rendering context for a given HTML canvas.
+/// The whole chart is drawn with `draw_chart` instead when the grid is not cached
+/// (e.g. on the first draw or after a resize), when the layout of the time axis
+/// changes, or when a setting makes one segment affect how others are drawn:
+/// `GridMode::TransitionsOnly`, `highlight_overage`, `split_sleeper_brackets`,
+/// `show_odometer`, and `side_panel`.
 ///
 /// # Parameters
-/// - `canvas`: A reference to the HTML canvas element.
+/// - `previous`: The segments the chart was last drawn with, using the same `props`.
+/// - `segments`: The new segments.
+/// - `props`: A reference to `ChartProps` defining the chart's visual settings.
 ///
 /// # Returns
-/// - `Ok(CanvasRenderingContext2d)`: If the 2D context is available.
-/// - `Err(String)`: If the context cannot be obtained or cast.
-fn get_canvas_context(canvas: &HtmlCanvasElement) -> Result<CanvasRenderingContext2d, String> {
-    canvas
-        .get_context("2d")
-        .map_err(|_| "Failed to get 2D context".to_string())?
-        .ok_or_else(|| "2D context is unavailable".to_string())?
-        .dyn_into::<CanvasRenderingContext2d>()
-        .map_err(|_| "Failed to cast context to CanvasRenderingContext2d".to_string())
-}
-
-/// Checks whether the grid has already been drawn on the canvas.
+/// - `Ok(&ChartProps)`: If the chart was successfully redrawn.
+/// - `Err(ChartError)`: If an error occurred (e.g., unable to find the canvas).
+pub fn redraw_chart<'a>(
+    previous: &[Segment],
+    segments: &'a [Segment],
+    props: &'a ChartProps,
+) -> Result<&'a ChartProps, ChartError> {
+    if !supports_partial_redraw(props) {
+        return draw_chart(segments, props);
+    }
+    let canvas = get_canvas(&props.canvas_id)?;
+    let context = get_canvas_context(&canvas)?;
+
+    let (canvas_width, height) = (props.width as f64, props.height as f64);
+    let width = content_width(canvas_width, props);
+    let pixel_ratio = device_pixel_ratio();
+    if size_canvas(&canvas, props, pixel_ratio) {
+        forget_grid(&props.canvas_id);
+        return draw_chart(segments, props);
+    }
+
+    let drawable = |segment: &&Segment| check_drawable(segment).is_ok();
+    let (old_segments, old_layout) =
+        lay_out(previous.iter().filter(drawable).cloned().collect(), props);
+    let (new_segments, layout) = lay_out(sanitize_segments(segments), props);
+    let fingerprint = grid_fingerprint(canvas_width, height, pixel_ratio, &layout);
+    if layout.hours_span != old_layout.hours_span
+        || !grid_already_drawn(&props.canvas_id, fingerprint)
+    {
+        return draw_chart(segments, props);
+    }
+
+    let regions = changed_regions(&old_segments, &new_segments, width, &layout);
+    if regions.is_empty() {
+        return Ok(props);
+    }
+
+    context.save();
+    scale_context(&context, pixel_ratio);
+    if width < canvas_width {
+        context
+            .translate(align_offset(canvas_width, props), 0.0)
+            .unwrap_or_else(|_| log::warn!("Failed to align the chart"));
+    }
+    if layout.corner_radius > 0.0 {
+        clip_rounded_corners(&context, width, height, layout.corner_radius);
+    }
+    context.begin_path();
+    for (x_start, x_end) in regions {
+        context.rect(x_start, 0.0, x_end - x_start, height);
+    }
+    context.clip();
+
+    draw_grid(&context, &new_segments, width, height, &layout);
+    draw_layers(&context, &new_segments, width, height, &layout);
+    context.restore();
+    Ok(props)
+}
+
+/// Picks the segments a chart can be redrawn against with `redraw_chart`.
+///
+/// A partial redraw only applies when the chart was last drawn with exactly the same
+/// props; otherwise the whole chart is drawn with `draw_chart`.
 ///
-/// The function looks for an HTML element with the ID `"grid-drawn"`, which acts as a flag.
+/// # Parameters
+/// - `previous`: The segments the chart was last drawn with, if it was drawn.
+/// - `drawn_props`: The props the chart was last drawn with, if it was drawn.
+/// - `props`: The props the chart is about to be drawn with.
 ///
 /// # Returns
-/// - `Ok(true)`: If the grid has already been drawn.
-/// - `Ok(false)`: If the grid has not been drawn.
-/// - `Err(String)`: If an error occurs while accessing the document.
-fn grid_already_drawn() -> Result<bool, String> {
-    let document = window()
-        .ok_or("No Window found".to_string())?
-        .document()
-        .ok_or("No Document found".to_string())?;
-    if let Some(existing_grid) = document.get_element_by_id("grid-drawn") {
-        return Ok(existing_grid.get_attribute("data-drawn") == Some("true".to_string()));
-    }
-    Ok(false)
+/// - `Option<&[Segment]>`: The segments to pass to `redraw_chart` as `previous`, or
+///   `None` if the chart needs a full `draw_chart`.
+pub fn redraw_base<'a>(
+    previous: Option<&'a [Segment]>,
+    drawn_props: Option<&ChartProps>,
+    props: &ChartProps,
+) -> Option<&'a [Segment]> {
+    previous.filter(|_| drawn_props == Some(props))
 }
 
-/// Marks the grid as drawn by adding a hidden HTML element.
+/// Checks whether `redraw_chart` can repaint only the changed parts of the chart.
+fn supports_partial_redraw(props: &ChartProps) -> bool {
+    props.grid_mode != GridMode::TransitionsOnly
+        && !props.highlight_overage
+        && !props.split_sleeper_brackets
+        && !props.show_odometer
+        && !props.side_panel
+}
+
+/// Extra width, in pixels, repainted on each side of a changed range, covering the
+/// line caps and vertical steps drawn at its edges.
+const REDRAW_PADDING_PX: f64 = 4.0;
+
+/// Returns the x-ranges of the canvas that `redraw_chart` repaints.
 ///
-/// This function creates a `<div>` element with the ID `"grid-drawn"` and a `data-drawn="true"`
-/// attribute, which serves as a flag indicating that the grid has already been rendered.
+/// # Parameters
+/// - `previous`: The segments last drawn, after `lay_out`.
+/// - `segments`: The new segments, after `lay_out`.
+/// - `width`: The width of the chart.
+/// - `props`: The chart properties the segments are laid out with.
 ///
 /// # Returns
-/// - `Ok(())`: If the marker was successfully added.
-/// - `Err(String)`: If an error occurs while modifying the DOM.
-fn mark_grid_as_drawn() -> Result<(), String> {
-    let document = window()
-        .ok_or("No Window found".to_string())?
-        .document()
-        .ok_or("No Document found".to_string())?;
-    let grid_marker = document
-        .create_element("div")
-        .map_err(|_| "Failed to create grid marker".to_string())?;
-    grid_marker.set_id("grid-drawn");
-    grid_marker
-        .set_attribute("data-drawn", "true")
-        .map_err(|_| "Failed to set attribute".to_string())?;
-    document
-        .body()
-        .ok_or("No body found".to_string())?
-        .append_child(&grid_marker)
-        .map_err(|_| "Failed to append child".to_string())?;
-    Ok(())
+/// - `Vec<(f64, f64)>`: The `(x_start, x_end)` ranges, in CSS pixels.
+fn changed_regions(
+    previous: &[Segment],
+    segments: &[Segment],
+    width: f64,
+    props: &ChartProps,
+) -> Vec<(f64, f64)> {
+    let padding = REDRAW_PADDING_PX
+        + props.min_segment_px
+        + props
+            .segment_border
+            .as_ref()
+            .map_or(0.0, |(_, line_width)| *line_width);
+    diff_logs(previous, segments)
+        .into_iter()
+        .map(|(start, end)| {
+            (
+                hour_to_x(start, width, props) - padding,
+                hour_to_x(end, width, props) + padding,
+            )
+        })
+        .collect()
 }
 
-/// Draws the background grid on the canvas.
+/// Lays out drawable segments the way `draw_chart` does, completing the day with
+/// `end_of_day_status` and fitting the time axis to them.
 ///
-/// The grid consists of horizontal and vertical lines that divide the chart
-/// into sections representing hours and duty statuses. Labels for time and
-/// status categories are also drawn.
+/// # Returns
+/// - `(Vec<Segment>, ChartProps)`: The segments to draw and the props with the fitted
+///   `hours_span`.
+fn lay_out(segments: Vec<Segment>, props: &ChartProps) -> (Vec<Segment>, ChartProps) {
+    let segments = fill_end_of_day(&segments, props);
+    let (segments, hours_span) = fit_to_span(&segments, props);
+    let layout = ChartProps {
+        hours_span,
+        ..props.clone()
+    };
+    (segments, layout)
+}
+
+/// Draws the segments and everything layered over them, once the grid is in place.
 ///
 /// # Parameters
 /// - `context`: The 2D rendering context.
-/// - `width`: The width of the canvas.
+/// - `segments`: The segments, after `lay_out`.
+/// - `width`: The width of the chart.
 /// - `height`: The height of the canvas.
-/// - `props`: The chart properties, including colors and font settings.
-fn draw_grid(context: &CanvasRenderingContext2d, width: f64, height: f64, props: &ChartProps) {
-    context.clear_rect(0.0, 0.0, width, height);
+/// - `layout`: The chart properties returned by `lay_out`.
+fn draw_layers(
+    context: &CanvasRenderingContext2d,
+    segments: &[Segment],
+    width: f64,
+    height: f64,
+    layout: &ChartProps,
+) {
+    draw_segments(context, segments, width, height, layout);
+    if layout.split_sleeper_brackets {
+        draw_split_sleeper_brackets(context, segments, width, height, layout);
+    }
+    if layout.show_odometer {
+        draw_odometer_readings(context, segments, width, height, layout);
+    }
+    if let Some(hour) = layout.now_hour {
+        draw_now_marker(context, hour, width, height, layout);
+    }
+    draw_annotations(context, width, height, layout);
+    if layout.side_panel {
+        draw_side_panel(context, segments, width, height, layout);
+    }
+}
+
+/// Returns the width the chart is drawn in on a canvas of the given width.
+///
+/// The chart fills the canvas, up to `ChartProps::max_width`.
+pub(crate) fn content_width(canvas_width: f64, props: &ChartProps) -> f64 {
+    props
+        .max_width
+        .map_or(canvas_width, |max| canvas_width.min(max as f64))
+}
+
+/// Returns how far right the chart is shifted on a canvas of the given width.
+///
+/// # Parameters
+/// - `canvas_width`: The width of the canvas.
+/// - `props`: The chart properties holding `max_width` and `align`.
+///
+/// # Returns
+/// - `f64`: The x-offset of the chart's left edge, `0.0` when it fills the canvas.
+pub(crate) fn align_offset(canvas_width: f64, props: &ChartProps) -> f64 {
+    let free = canvas_width - content_width(canvas_width, props);
+    let offset = match props.align {
+        Align::Left => 0.0,
+        Align::Center => free / 2.0,
+        Align::Right => free,
+    };
+    snap(offset, props)
+}
 
-    let padding_x = 70.0;
-    let padding_y = 40.0;
-    let row_height = (height - 2.0 * padding_y) / 4.0;
-    let col_width = (width - 2.0 * padding_x) / 24.0;
+/// Picks the canvas width for a container of the given size.
+///
+/// With neither `min_width` nor `max_width` set, the chart is not responsive and keeps
+/// its fixed `width`. Otherwise it fills the container, clamped to the bounds that are
+/// set.
+///
+/// # Parameters
+/// - `container_width`: The width of the element wrapping the canvas, in pixels.
+/// - `props`: The chart properties holding `width`, `min_width`, and `max_width`.
+///
+/// # Returns
+/// - `u32`: The width to give the canvas.
+pub fn effective_width(container_width: f64, props: &ChartProps) -> u32 {
+    if props.min_width.is_none() && props.max_width.is_none() {
+        return props.width;
+    }
+    let min = props.min_width.unwrap_or(0);
+    let max = props.max_width.unwrap_or(u32::MAX).max(min);
+    (container_width.max(0.0) as u32).clamp(min, max)
+}
 
-    let statuses = ["Off Duty", "Sleeper", "Driving", "On Duty"];
-    let hours = generate_hour_labels();
+/// Picks the `class` attribute of the `div` wrapping the chart canvas.
+///
+/// Surrounding whitespace is trimmed, and a blank class leaves the attribute off
+/// instead of rendering an empty `class=""`.
+///
+/// # Parameters
+/// - `class`: The CSS class given to the chart component.
+///
+/// # Returns
+/// - `Option<&str>`: The class to render, or `None` to omit the attribute.
+pub fn container_class(class: &str) -> Option<&str> {
+    Some(class.trim()).filter(|class| !class.is_empty())
+}
 
-    context.set_stroke_style_str(props.grid_color);
-    context.set_fill_style_str(props.label_color);
-    context.set_font(props.font);
+/// Fits the segments to the time axis according to `ChartProps::on_overflow`.
+///
+/// Segments running past `hours_span` are clipped at the edge (`OverflowMode::Clip`),
+/// wrapped around to the start of the axis (`OverflowMode::Wrap`), or left untouched
+/// while the span grows to the next whole hour that covers them (`OverflowMode::Extend`).
+///
+/// # Parameters
+/// - `segments`: A slice of `Segment` structs representing the driver's log.
+/// - `props`: The chart properties holding `hours_span` and `on_overflow`.
+///
+/// # Returns
+/// - `(Vec<Segment>, f32)`: The fitted segments and the effective hours span.
+pub fn fit_to_span(segments: &[Segment], props: &ChartProps) -> (Vec<Segment>, f32) {
+    let span = props.hours_span;
 
-    for i in 0..=4 {
-        let y = padding_y + i as f64 * row_height;
-        context.begin_path();
-        context.move_to(padding_x, y);
-        context.line_to(width, y);
-        context.stroke();
+    match props.on_overflow {
+        OverflowMode::Clip => {
+            let clipped = segments
+                .iter()
+                .filter(|segment| segment.start_hour < span)
+                .map(|segment| Segment {
+                    end_hour: segment.end_hour.min(span),
+                    ..segment.clone()
+                })
+                .collect();
+            (clipped, span)
+        }
+        OverflowMode::Wrap => {
+            let mut wrapped = Vec::new();
+            for segment in segments {
+                let mut start = segment.start_hour;
+                while start < segment.end_hour {
+                    let offset = (start / span).floor() * span;
+                    let end = segment.end_hour.min(offset + span);
+                    wrapped.push(Segment {
+                        start_hour: start - offset,
+                        end_hour: end - offset,
+                        ..segment.clone()
+                    });
+                    start = end;
+                }
+            }
+            (wrapped, span)
+        }
+        OverflowMode::Extend => {
+            let end = segments
+                .iter()
+                .map(|segment| segment.end_hour)
+                .fold(span, f32::max);
+            (segments.to_vec(), end.ceil())
+        }
+    }
+}
 
-        if i < 4 {
-            context
-                .fill_text(statuses[i], 10.0, y + row_height / 2.0)
-                .unwrap_or_else(|_| log::warn!("Failed to draw text"));
+/// Fills the rest of the day after the last segment with `end_of_day_status`.
+///
+/// The last segment is extended when it already has that status; otherwise a new
+/// segment running to hour 24 is appended. Logs that reach the end of the day, empty
+/// logs, and every log when `end_of_day_status` is `None` are returned unchanged.
+///
+/// # Parameters
+/// - `segments`: A slice of `Segment` structs representing the driver's log.
+/// - `props`: The chart properties holding `end_of_day_status`.
+///
+/// # Returns
+/// - `Vec<Segment>`: The segments, covering the day up to hour 24 unless
+///   `end_of_day_status` is `None`.
+pub fn fill_end_of_day(segments: &[Segment], props: &ChartProps) -> Vec<Segment> {
+    let mut filled = segments.to_vec();
+    let Some(end_of_day_status) = &props.end_of_day_status else {
+        return filled;
+    };
+    let last = filled
+        .iter_mut()
+        .max_by(|a, b| a.end_hour.total_cmp(&b.end_hour));
+    match last {
+        Some(last) if last.end_hour < 24.0 && last.status == *end_of_day_status => {
+            last.end_hour = 24.0;
         }
+        Some(last) if last.end_hour < 24.0 => {
+            let start_hour = last.end_hour;
+            filled.push(Segment::builder(start_hour, 24.0, end_of_day_status.clone()).build());
+        }
+        _ => {}
     }
+    filled
+}
 
-    context.set_font("12px Arial");
+/// Drops the segments that fail `validate_segment`, logging why each one was skipped.
+///
+/// Segments that only run past the end of the day are kept, since
+/// `ChartProps::on_overflow` decides how they are drawn.
+fn sanitize_segments(segments: &[Segment]) -> Vec<Segment> {
+    segments
+        .iter()
+        .enumerate()
+        .filter_map(|(i, segment)| match check_drawable(segment) {
+            Ok(()) => Some(segment.clone()),
+            Err(err) => {
+                log::warn!("Skipping segment {}: {}", i, err);
+                None
+            }
+        })
+        .collect()
+}
 
-    for i in 0..25 {
-        let x = padding_x + i as f64 * col_width;
-        context.begin_path();
-        context.move_to(x, padding_y);
-        context.line_to(x, height);
+/// Checks whether `sanitize_segments` keeps a segment.
+fn check_drawable(segment: &Segment) -> Result<(), SegmentErrorKind> {
+    match validate_segment(segment) {
+        Err(SegmentErrorKind::OutOfBounds) if segment.start_hour >= 0.0 => Ok(()),
+        result => result,
+    }
+}
 
-        context.set_stroke_style_str(props.grid_color);
-        context.stroke();
+/// Distance, in pixels, from a row line within which `segment_at` finds no segment.
+const ROW_LINE_HIT_TOLERANCE: f64 = 2.0;
 
-        if i % 2 == 0 {
-            context
-                .fill_text(&hours[i], x - 10.0, height - 10.0)
-                .unwrap_or_else(|_| log::warn!("Failed to draw text"));
+/// Finds the segment drawn at a point of the chart, e.g. under a mouse click.
+///
+/// The point is mapped back onto the time axis and status rows with the same layout
+/// `draw_chart` uses, including `align`, `hours_span`, `on_overflow`, and
+/// `min_segment_px`. Points within a couple of pixels of the line between two rows
+/// belong to neither row. Where segments overlap, the one listed last, which is
+/// drawn on top, is returned. With `ChartStyle::Ribbon`, any point in the band matches.
+///
+/// # Parameters
+/// - `segments`: A slice of `Segment` structs representing the driver's log.
+/// - `props`: The chart properties the chart was drawn with.
+/// - `x`: The x-coordinate of the point, in CSS pixels from the canvas's left edge.
+/// - `y`: The y-coordinate of the point, in CSS pixels from the canvas's top edge.
+///
+/// # Returns
+/// - `Some(usize)`: The index in `segments` of the segment at the point.
+/// - `None`: If no segment is drawn there, including the hours `draw_chart` fills with
+///   `end_of_day_status`.
+pub fn segment_at(segments: &[Segment], props: &ChartProps, x: f64, y: f64) -> Option<usize> {
+    let drawable: Vec<Segment> = segments
+        .iter()
+        .filter(|segment| check_drawable(segment).is_ok())
+        .cloned()
+        .collect();
+    let (_, hours_span) = fit_to_span(&fill_end_of_day(&drawable, props), props);
+    let layout = ChartProps {
+        hours_span,
+        ..props.clone()
+    };
+    let (canvas_width, height) = (props.width as f64, props.height as f64);
+    let width = content_width(canvas_width, props);
+    let x = x - align_offset(canvas_width, props);
+
+    let row = if layout.style == ChartStyle::Ribbon {
+        let (top, bottom) = (row_y(0, height, &layout), row_y(4, height, &layout));
+        if y < top || y > bottom {
+            return None;
+        }
+        None
+    } else {
+        Some(row_at(y, height, &layout)?)
+    };
+
+    segments
+        .iter()
+        .enumerate()
+        .rev()
+        .filter(|(_, segment)| check_drawable(segment).is_ok())
+        .filter(|(_, segment)| row.is_none_or(|row| status_row(&segment.status) == row))
+        .find(|(_, segment)| {
+            fit_to_span(std::slice::from_ref(*segment), &layout)
+                .0
+                .iter()
+                .filter_map(|piece| clip_to_axis(piece, &layout))
+                .any(|piece| {
+                    let (x_start, x_end) = segment_x_range(&piece, width, &layout);
+                    (x_start..=x_end).contains(&x)
+                })
+        })
+        .map(|(index, _)| index)
+}
+
+/// Returns the logical status row at a y-coordinate, or `None` outside the rows or on a
+/// row line.
+fn row_at(y: f64, height: f64, props: &ChartProps) -> Option<usize> {
+    (0..4)
+        .find(|&row| {
+            y > row_y(row, height, props) + ROW_LINE_HIT_TOLERANCE
+                && y < row_y(row + 1, height, props) - ROW_LINE_HIT_TOLERANCE
+        })
+        .map(|row| display_row(row, props))
+}
+
+/// Builds a footer recording when the chart was generated.
+///
+/// The timestamp comes from the browser clock (`js_sys::Date`) in ISO 8601 format,
+/// e.g. `"Generated 2025-03-14T15:09:26.535Z"`, and is meant for `ChartProps::footer`.
+///
+/// # Returns
+/// - `String`: The footer text.
+pub fn generated_footer() -> String {
+    format!("Generated {}", String::from(Date::new_0().to_iso_string()))
+}
+
+/// Clears a chart's canvas and marks its grid as not drawn.
+///
+/// # Parameters
+/// - `canvas_id`: The `id` of the chart's `<canvas>`, as in `ChartProps::canvas_id`.
+///
+/// # Returns
+/// - `Ok(())`: If the canvas was cleared.
+/// - `Err(ChartError)`: If the canvas or its context cannot be found.
+pub fn clear_chart(canvas_id: &str) -> Result<(), ChartError> {
+    let canvas = get_canvas(canvas_id)?;
+    let context = get_canvas_context(&canvas)?;
+    context.clear_rect(0.0, 0.0, canvas.width() as f64, canvas.height() as f64);
+    forget_grid(canvas_id);
+    Ok(())
+}
+
+/// Fraction of each availability bar, at its end, shaded as the red zone.
+const RED_ZONE_FRACTION: f64 = 0.1;
+
+/// Draws the drive, shift, and cycle clocks as progress bars above the grid.
+///
+/// The three bars sit side by side in the top padding of the chart. Each one is filled
+/// proportionally to the hours used, in `driving_color`, over a track whose last tenth
+/// is tinted in `overage_color` as the zone near the limit. Call it after `draw_chart`,
+/// since redrawing the grid clears the whole canvas.
+///
+/// # Parameters
+/// - `clocks`: The hours left on each clock, e.g. from `hos::remaining_clocks`.
+/// - `props`: The chart properties defining colors and layout.
+///
+/// # Returns
+/// - `Ok(())`: If the bars were drawn.
+/// - `Err(ChartError)`: If the canvas or its context cannot be found.
+pub fn draw_availability_bars(clocks: &Clocks, props: &ChartProps) -> Result<(), ChartError> {
+    let canvas = get_canvas(&props.canvas_id)?;
+    let context = get_canvas_context(&canvas)?;
+    let canvas_width = props.width as f64;
+    let width = content_width(canvas_width, props);
+
+    context.save();
+    scale_context(&context, device_pixel_ratio());
+    context
+        .translate(align_offset(canvas_width, props), 0.0)
+        .unwrap_or_else(|_| log::warn!("Failed to align the chart"));
+
+    let slot_width = (grid_right(width, props) - PADDING_X) / 3.0;
+    let bar_width = slot_width - 20.0;
+    let (bar_y, bar_height) = (PADDING_Y - 16.0, 8.0);
+
+    context.clear_rect(
+        PADDING_X,
+        0.0,
+        grid_right(width, props) - PADDING_X,
+        PADDING_Y - 4.0,
+    );
+    context.set_font("10px Arial");
+    for (i, (label, left, limit)) in clocks.with_limits().into_iter().enumerate() {
+        let x = PADDING_X + i as f64 * slot_width;
+        let used = ((limit - left) / limit).clamp(0.0, 1.0) as f64;
+
+        context.set_fill_style_str(&props.grid_color);
+        context.fill_rect(x, bar_y, bar_width, bar_height);
+        context.set_fill_style_str(&props.overage_color);
+        context.fill_rect(
+            x + bar_width * (1.0 - RED_ZONE_FRACTION),
+            bar_y,
+            bar_width * RED_ZONE_FRACTION,
+            bar_height,
+        );
+        context.set_fill_style_str(&props.driving_color);
+        context.fill_rect(x, bar_y, bar_width * used, bar_height);
+
+        context.set_fill_style_str(&props.label_color);
+        context
+            .fill_text(&format!("{label}: {left:.1}h left"), x, bar_y - 4.0)
+            .unwrap_or_else(|_| log::warn!("Failed to draw text"));
+    }
+    context.restore();
+    Ok(())
+}
+
+/// The identifying details printed above a log for roadside inspections.
+///
+/// # Fields
+/// - `carrier`: The motor carrier's name.
+/// - `dot_number`: The carrier's USDOT number.
+/// - `driver`: The driver's name.
+/// - `co_driver`: The co-driver's name, empty when driving solo.
+/// - `vehicle`: The truck or tractor number.
+/// - `trailer`: The trailer number(s).
+/// - `date`: The day of the log.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct LogHeader {
+    pub carrier: String,
+    pub dot_number: String,
+    pub driver: String,
+    pub co_driver: String,
+    pub vehicle: String,
+    pub trailer: String,
+    pub date: String,
+}
+
+impl LogHeader {
+    /// Lists each field as `(label, value)`, in the order `draw_header` lays them out.
+    pub fn fields(&self) -> [(&'static str, &str); 7] {
+        [
+            ("Carrier", &self.carrier),
+            ("USDOT #", &self.dot_number),
+            ("Date", &self.date),
+            ("Driver", &self.driver),
+            ("Co-Driver", &self.co_driver),
+            ("Vehicle", &self.vehicle),
+            ("Trailer", &self.trailer),
+        ]
+    }
+}
+
+/// Number of cells per row of the header drawn by `draw_header`.
+const HEADER_COLUMNS: usize = 4;
+
+/// Draws a log header block above the grid, as on a printed inspection log.
+///
+/// The fields of `header` (see `LogHeader::fields`) fill a two-row table of labeled
+/// cells in the top padding of the chart, outlined in `grid_color`, each reading
+/// `"Label: value"` in `label_color`. Call it after `draw_chart`, since
+/// redrawing the grid clears the whole canvas.
+///
+/// # Parameters
+/// - `header`: The carrier, driver, and vehicle details to print.
+/// - `props`: The chart properties defining colors and layout.
+///
+/// # Returns
+/// - `Ok(())`: If the header was drawn.
+/// - `Err(ChartError)`: If the canvas or its context cannot be found.
+pub fn draw_header(header: &LogHeader, props: &ChartProps) -> Result<(), ChartError> {
+    let canvas = get_canvas(&props.canvas_id)?;
+    let context = get_canvas_context(&canvas)?;
+    let canvas_width = props.width as f64;
+    let width = content_width(canvas_width, props);
+
+    context.save();
+    scale_context(&context, device_pixel_ratio());
+    context
+        .translate(align_offset(canvas_width, props), 0.0)
+        .unwrap_or_else(|_| log::warn!("Failed to align the chart"));
+
+    let table_width = grid_right(width, props) - PADDING_X;
+    let cell_width = table_width / HEADER_COLUMNS as f64;
+    let cell_height = (PADDING_Y - 4.0) / 2.0;
+
+    context.clear_rect(PADDING_X, 0.0, table_width, PADDING_Y - 4.0);
+    context.set_line_width(1.0);
+    context.set_stroke_style_str(&props.grid_color);
+    context.set_fill_style_str(&props.label_color);
+    context.set_font("10px Arial");
+    for (i, (label, value)) in header.fields().into_iter().enumerate() {
+        let x = PADDING_X + (i % HEADER_COLUMNS) as f64 * cell_width;
+        let y = (i / HEADER_COLUMNS) as f64 * cell_height;
+        context.stroke_rect(x, y, cell_width, cell_height);
+
+        context
+            .fill_text(&format!("{label}: {value}"), x + 4.0, y + cell_height - 5.0)
+            .unwrap_or_else(|_| log::warn!("Failed to draw text"));
+    }
+    context.restore();
+    Ok(())
+}
+
+/// Gap, in pixels, left between neighbouring hour blocks.
+const HOUR_BLOCK_GAP: f64 = 1.0;
+
+/// Draws the day as a strip of 24 square blocks, one per hour.
+///
+/// Each block is filled with the color of the status covering most of its hour (see
+/// `bucket_statuses`); hours no segment touches are left in `background_color` with a
+/// `grid_color` outline. The strip is as wide as the canvas allows and vertically
+/// centered, which makes a glanceable view for small spaces such as calendar cells.
+///
+/// The whole canvas is cleared, so the next `draw_chart` on it redraws the grid.
+///
+/// # Parameters
+/// - `segments`: A slice of `Segment` structs representing the driver's log.
+/// - `props`: The chart properties defining the canvas and colors.
+///
+/// # Returns
+/// - `Ok(())`: If the blocks were drawn.
+/// - `Err(ChartError)`: If the canvas or its context cannot be found.
+pub fn draw_hour_blocks(segments: &[Segment], props: &ChartProps) -> Result<(), ChartError> {
+    let canvas = get_canvas(&props.canvas_id)?;
+    let context = get_canvas_context(&canvas)?;
+    let (width, height) = (props.width as f64, props.height as f64);
+    let pixel_ratio = device_pixel_ratio();
+    size_canvas(&canvas, props, pixel_ratio);
+    forget_grid(&props.canvas_id);
+
+    context.save();
+    scale_context(&context, pixel_ratio);
+    context.clear_rect(0.0, 0.0, width, height);
+
+    let size = (width / 24.0).min(height);
+    let top = (height - size) / 2.0;
+    context.set_line_width(1.0);
+    context.set_stroke_style_str(&props.grid_color);
+    for (hour, status) in bucket_statuses(segments, 60).iter().enumerate() {
+        let x = hour as f64 * size;
+        let block = size - HOUR_BLOCK_GAP;
+        match status {
+            Some(status) => {
+                context.set_fill_style_str(props.color_for(status));
+                context.fill_rect(x, top, block, block);
+            }
+            None => {
+                context.set_fill_style_str(&props.background_color);
+                context.fill_rect(x, top, block, block);
+                context.stroke_rect(x, top, block, block);
+            }
         }
     }
+    context.restore();
+    Ok(())
 }
 
-/// Draws the duty status segments on the chart.
+/// Draws the logs of a driving team on one grid.
 ///
-/// Each segment is represented as a colored line corresponding to the
-/// driver's status within a given time range.
+/// Each status row is shared by both drivers: driver A's segments are drawn as solid
+/// lines in the upper part of the row and driver B's as lines dashed with
+/// `driver_b_dash` slightly below, so both stay readable where they overlap. Segments
+/// are always drawn as lines, whatever `style` and `segment_style` are set to.
+///
+/// The grid is redrawn on every call, and the next `draw_chart` on the same canvas
+/// redraws it as well.
 ///
 /// # Parameters
-/// - `context`: The 2D rendering context.
-/// - `segments`: A slice of `Segment` structs.
-/// - `width`: The width of the canvas.
-/// - `height`: The height of the canvas.
-/// - `props`: The chart properties defining colors and styles.
-fn draw_segments(
-    context: &CanvasRenderingContext2d,
-    segments: &[Segment],
-    width: f64,
-    height: f64,
+/// - `driver_a`: The segments of the first driver's log.
+/// - `driver_b`: The segments of the second driver's log.
+/// - `props`: The chart properties defining colors and layout.
+///
+/// # Returns
+/// - `Ok(())`: If the chart was drawn.
+/// - `Err(ChartError)`: If the canvas or its context cannot be found.
+pub fn draw_team(
+    driver_a: &[Segment],
+    driver_b: &[Segment],
     props: &ChartProps,
-) {
-    let padding_x = 70.0;
-    let padding_y = 40.0;
-    let row_height = (height - 2.0 * padding_y) / 4.0;
-    let col_width = (width - 2.0 * padding_x) / 24.0;
+) -> Result<(), ChartError> {
+    let canvas = get_canvas(&props.canvas_id)?;
+    let context = get_canvas_context(&canvas)?;
+    let (width, height) = (props.width as f64, props.height as f64);
+    let pixel_ratio = device_pixel_ratio();
+    size_canvas(&canvas, props, pixel_ratio);
 
-    context.set_line_width(4.0);
+    let (driver_a, span_a) = fit_to_span(&sanitize_segments(driver_a), props);
+    let (driver_b, span_b) = fit_to_span(&sanitize_segments(driver_b), props);
+    let layout = ChartProps {
+        hours_span: span_a.max(span_b),
+        ..props.clone()
+    };
+    let both: Vec<Segment> = driver_a.iter().chain(&driver_b).cloned().collect();
 
-    for segment in segments {
-        let y_index = match segment.status {
-            DutyStatus::OffDuty => 0,
-            DutyStatus::Sleeper => 1,
-            DutyStatus::Driving => 2,
-            DutyStatus::OnDuty => 3,
-            DutyStatus::PersonalConveyance | DutyStatus::YardMove => 999, // TODO: add to chart
-        };
+    context.save();
+    scale_context(&context, pixel_ratio);
+    if layout.corner_radius > 0.0 {
+        clip_rounded_corners(&context, width, height, layout.corner_radius);
+    }
+    draw_grid(&context, &both, width, height, &layout);
+    forget_grid(&props.canvas_id);
 
-        let y_val = padding_y + (y_index as f64) * row_height + (row_height / 2.0);
-        let x_start = padding_x + (segment.start_hour as f64) * col_width;
-        let x_end = padding_x + (segment.end_hour as f64) * col_width;
+    context.set_line_width(3.0);
+    for (segments, second_driver) in [(&driver_a, false), (&driver_b, true)] {
+        for segment in segments {
+            let Some(segment) = clip_to_axis(segment, &layout) else {
+                continue;
+            };
+            let y_val = team_y(&segment.status, second_driver, height, &layout);
+            let (x_start, x_end) = segment_x_range(&segment, width, &layout);
+            if second_driver {
+                set_line_dash(&context, &layout.driver_b_dash);
+            } else if is_sub_status(&segment.status) {
+                set_line_dash(&context, &[6.0, 4.0]);
+            }
+            context.set_stroke_style_str(layout.color_for(&segment.status));
+            context.begin_path();
+            context.move_to(x_start, y_val);
+            context.line_to(x_end, y_val);
+            context.stroke();
+            set_line_dash(&context, &[]);
+        }
+    }
+    draw_annotations(&context, width, height, &layout);
+    context.restore();
+    Ok(())
+}
 
-        let color = match segment.status {
-            DutyStatus::OffDuty => props.off_duty_color,
-            DutyStatus::Sleeper => props.sleeper_color,
-            DutyStatus::Driving => props.driving_color,
-            DutyStatus::OnDuty => props.on_duty_color,
-            DutyStatus::PersonalConveyance | DutyStatus::YardMove => "",
-        };
+/// Forgets that the grid has been drawn, so the next `draw_chart` redraws it.
+///
+/// The grid cache is kept in memory per canvas id and outlives any single chart.
+/// This forgets the grids of every chart. Call this in the setup of every
+/// `wasm-bindgen-test` that draws a chart so each test starts from a clean cache.
+pub fn reset_grid_cache() {
+    DRAWN_GRIDS.with(|grids| grids.borrow_mut().clear());
+}
 
-        context.set_stroke_style_str(color);
-        context.begin_path();
-        context.move_to(x_start, y_val);
-        context.line_to(x_end, y_val);
-        context.stroke();
-    }
+/// Forgets the grid drawn on one canvas, e.g. when its component unmounts.
+pub(crate) fn forget_grid(canvas_id: &str) {
+    DRAWN_GRIDS.with(|grids| grids.borrow_mut().remove(canvas_id));
 }
 
-/// Generates a list of hour labels for the chart.
+/// Retrieves the 2D rendering context for a given HTML canvas.
 ///
-/// The function returns a vector of formatted hour labels in 12-hour AM/PM format,
-/// ranging from "12 AM" to "12 AM" (covering a full 24-hour period).
+/// # Parameters
+/// - `canvas`: A reference to the HTML canvas element.
 ///
 /// # Returns
-/// - `Vec<String>`: A vector containing formatted hour labels.
-fn generate_hour_labels() -> Vec<String> {
-    let mut hours: Vec<String> = (0..24)
-        .map(|h| {
-            format!(
-                "{} {}",
-                if h == 0 || h == 12 { 12 } else { h % 12 },
-                if h < 12 { "AM" } else { "PM" }
-            )
-        })
-        .collect();
+/// - `Ok(HtmlCanvasElement)`: The HTML canvas element.
+/// - `Err(ChartError)`: If the canvas cannot be found or is not a canvas.
+fn get_canvas(id: &str) -> Result<HtmlCanvasElement, ChartError> {
+    window()
+        .ok_or(ChartError::NoWindow)?
+        .document()
+        .ok_or(ChartError::NoDocument)?
+        .get_element_by_id(id)
+        .ok_or_else(|| ChartError::CanvasNotFound(id.to_string()))?
+        .dyn_into::<HtmlCanvasElement>()
+        .map_err(|_| ChartError::CastFailed)
+}
 
-    hours.push("12 AM".to_string());
-    hours
+/// Retrieves the 2D rendering context for a given HTML canvas.
+///
+/// # Parameters
+/// - `canvas`: A reference to the HTML canvas element.
+///
+/// # Returns
+/// - `Ok(CanvasRenderingContext2d)`: If the 2D context is available.
+/// - `Err(ChartError)`: If the context cannot be obtained or cast.
+fn get_canvas_context(canvas: &HtmlCanvasElement) -> Result<CanvasRenderingContext2d, ChartError> {
+    canvas
+        .get_context("2d")
+        .map_err(|err| ChartError::DomError(format!("{:?}", err)))?
+        .ok_or(ChartError::ContextUnavailable)?
+        .dyn_into::<CanvasRenderingContext2d>()
+        .map_err(|_| ChartError::CastFailed)
+}
+
+/// Returns the browser's device pixel ratio, or `1.0` when it is unavailable.
+fn device_pixel_ratio() -> f64 {
+    window()
+        .map(|window| window.device_pixel_ratio())
+        .filter(|ratio| *ratio > 0.0)
+        .unwrap_or(1.0)
+}
+
+/// Sizes the canvas for crisp drawing on high-density displays.
+///
+/// The backing store is made `pixel_ratio` times the chart's `width` and `height`,
+/// while the canvas keeps its logical size on the page through its CSS size.
+///
+/// # Parameters
+/// - `canvas`: The chart's canvas.
+/// - `props`: The chart properties holding the logical `width` and `height`.
+/// - `pixel_ratio`: The device pixel ratio to draw at.
+///
+/// # Returns
+/// - `true`: If the backing store was resized, which clears the canvas.
+/// - `false`: If it already had the right size.
+fn size_canvas(canvas: &HtmlCanvasElement, props: &ChartProps, pixel_ratio: f64) -> bool {
+    let physical_width = (props.width as f64 * pixel_ratio).round() as u32;
+    let physical_height = (props.height as f64 * pixel_ratio).round() as u32;
+    let resized = canvas.width() != physical_width || canvas.height() != physical_height;
+    if resized {
+        canvas.set_width(physical_width);
+        canvas.set_height(physical_height);
+    }
+
+    let style = canvas.style();
+    style
+        .set_property("width", &format!("{}px", props.width))
+        .and_then(|_| style.set_property("height", &format!("{}px", props.height)))
+        .unwrap_or_else(|_| log::warn!("Failed to set the canvas size"));
+    resized
+}
+
+/// Scales the context so drawing in CSS pixels fills a backing store sized by `size_canvas`.
+fn scale_context(context: &CanvasRenderingContext2d, pixel_ratio: f64) {
+    if pixel_ratio != 1.0 {
+        context
+            .scale(pixel_ratio, pixel_ratio)
+            .unwrap_or_else(|_| log::warn!("Failed to scale the context"));
+    }
+}
+
+thread_local! {
+    /// The fingerprint of the grid last drawn on each canvas, keyed by canvas id.
+    static DRAWN_GRIDS: RefCell<HashMap<String, u64>> = RefCell::new(HashMap::new());
+}
+
+/// Checks whether the grid has already been drawn on the canvas with the same settings.
+///
+/// # Parameters
+/// - `canvas_id`: The `id` of the chart's `<canvas>`.
+/// - `fingerprint`: The fingerprint of the grid about to be drawn (see `grid_fingerprint`).
+///
+/// # Returns
+/// - `true`: If the canvas's last grid has the same fingerprint.
+/// - `false`: If the grid was never drawn, or was drawn with different settings.
+fn grid_already_drawn(canvas_id: &str, fingerprint: u64) -> bool {
+    DRAWN_GRIDS.with(|grids| grids.borrow().get(canvas_id) == Some(&fingerprint))
+}
+
+/// Records the fingerprint of the grid just drawn on the canvas.
+///
+/// # Parameters
+/// - `canvas_id`: The `id` of the chart's `<canvas>`.
+/// - `fingerprint`: The fingerprint of the grid that was drawn.
+fn mark_grid_as_drawn(canvas_id: &str, fingerprint: u64) {
+    DRAWN_GRIDS.with(|grids| {
+        grids
+            .borrow_mut()
+            .insert(canvas_id.to_string(), fingerprint)
+    });
+}
+
+/// Hashes the canvas size and every property that changes how the grid looks.
+///
+/// A different fingerprint means the cached grid is stale and must be redrawn.
+///
+/// # Parameters
+/// - `width`: The width of the canvas, in CSS pixels.
+/// - `height`: The height of the canvas, in CSS pixels.
+/// - `pixel_ratio`: The device pixel ratio the canvas is drawn at.
+/// - `props`: The chart properties used to draw the grid.
+///
+/// # Returns
+/// - `u64`: The fingerprint.
+fn grid_fingerprint(width: f64, height: f64, pixel_ratio: f64, props: &ChartProps) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    width.to_bits().hash(&mut hasher);
+    height.to_bits().hash(&mut hasher);
+    pixel_ratio.to_bits().hash(&mut hasher);
+    props.background_color.hash(&mut hasher);
+    props.grid_color.hash(&mut hasher);
+    props.font.hash(&mut hasher);
+    props.label_color.hash(&mut hasher);
+    (props.grid_mode as u8).hash(&mut hasher);
+    props.flip_y.hash(&mut hasher);
+    props.footer.hash(&mut hasher);
+    props.integer_layout.hash(&mut hasher);
+    props.hours_span.to_bits().hash(&mut hasher);
+    props.start_hour.to_bits().hash(&mut hasher);
+    props.end_hour.to_bits().hash(&mut hasher);
+    props.max_width.hash(&mut hasher);
+    (props.align as u8).hash(&mut hasher);
+    // The marker moves over the cached grid, so redraw the grid to erase the old one.
+    props.now_hour.map(f32::to_bits).hash(&mut hasher);
+    props.now_color.hash(&mut hasher);
+    props
+        .row_heights
+        .map(|weights| weights.map(f64::to_bits))
+        .hash(&mut hasher);
+    props.background_gradient.hash(&mut hasher);
+    props
+        .night_window
+        .map(|(start, end)| (start.to_bits(), end.to_bits()))
+        .hash(&mut hasher);
+    props.night_tint_color.hash(&mut hasher);
+    props.side_panel.hash(&mut hasher);
+    props.compact_labels.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Restricts drawing to a rounded rectangle covering the whole canvas.
+///
+/// The clip stays active until the surrounding `context.restore()`.
+///
+/// # Parameters
+/// - `context`: The 2D rendering context.
+/// - `width`: The width of the canvas.
+/// - `height`: The height of the canvas.
+/// - `radius`: The corner radius in pixels.
+fn clip_rounded_corners(context: &CanvasRenderingContext2d, width: f64, height: f64, radius: f64) {
+    context.begin_path();
+    context
+        .round_rect_with_f64(0.0, 0.0, width, height, radius)
+        .unwrap_or_else(|_| log::warn!("Failed to build rounded clip path"));
+    context.clip();
+}
+
+/// Draws the background grid on the canvas.
+///
+/// The grid consists of horizontal and vertical lines that divide the chart
+/// into sections representing hours and duty statuses. Labels for time and
+/// status categories are also drawn.
+///
+/// # Parameters
+/// - `context`: The 2D rendering context.
+/// - `segments`: The segments, used to place vertical lines in `GridMode::TransitionsOnly`.
+/// - `width`: The width of the canvas.
+/// - `height`: The height of the canvas.
+/// - `props`: The chart properties, including colors and font settings.
+fn draw_grid(
+    context: &CanvasRenderingContext2d,
+    segments: &[Segment],
+    width: f64,
+    height: f64,
+    props: &ChartProps,
+) {
+    context.clear_rect(0.0, 0.0, width, height);
+
+    if let Some((top_color, bottom_color)) = &props.background_gradient {
+        let gradient = context.create_linear_gradient(0.0, 0.0, 0.0, height);
+        gradient
+            .add_color_stop(0.0, top_color)
+            .and_then(|_| gradient.add_color_stop(1.0, bottom_color))
+            .unwrap_or_else(|_| log::warn!("Failed to add gradient color stop"));
+        context.set_fill_style_canvas_gradient(&gradient);
+        context.fill_rect(0.0, 0.0, width, height);
+    }
+
+    if let Some((night_start, night_end)) = props.night_window {
+        let ranges = if night_start <= night_end {
+            vec![(night_start, night_end)]
+        } else {
+            vec![(night_start, props.hours_span), (0.0, night_end)]
+        };
+        let (axis_start, axis_end) = axis_range(props);
+        let (top, bottom) = (row_y(0, height, props), row_y(4, height, props));
+        context.set_fill_style_str(&props.night_tint_color);
+        for (start, end) in ranges {
+            let (start, end) = (start.max(axis_start), end.min(axis_end));
+            if start >= end {
+                continue;
+            }
+            let x_start = hour_to_x(start, width, props);
+            let x_end = hour_to_x(end, width, props);
+            context.fill_rect(x_start, top, x_end - x_start, bottom - top);
+        }
+    }
+
+    let hours = generate_hour_labels(props);
+
+    context.set_stroke_style_str(&props.grid_color);
+    context.set_fill_style_str(&props.label_color);
+    context.set_font(&props.font);
+
+    for i in 0..=4 {
+        let y = row_y(i, height, props);
+        context.begin_path();
+        context.move_to(PADDING_X, y);
+        context.line_to(grid_right(width, props), y);
+        context.stroke();
+
+        if i < 4 {
+            let label_y = (y + row_y(i + 1, height, props)) / 2.0;
+            context
+                .fill_text(row_label(display_row(i, props), props), 10.0, label_y)
+                .unwrap_or_else(|_| log::warn!("Failed to draw text"));
+        }
+    }
+
+    let label_px = hour_label_font_px(width, props);
+    context.set_font(&format!("{label_px}px Arial"));
+
+    let top = row_y(0, height, props);
+    for hour in vertical_grid_hours(segments, props) {
+        let x = hour_to_x(hour, width, props);
+        draw_vertical_line(context, x, top, height, props);
+    }
+
+    for (hour, label) in hours.iter().filter(|(hour, _)| hour % 2 == 0) {
+        let x = hour_to_x(*hour as f32, width, props);
+        context
+            .fill_text(label, x - hour_label_offset(label_px), height - 10.0)
+            .unwrap_or_else(|_| log::warn!("Failed to draw text"));
+    }
+
+    if let Some(footer) = &props.footer {
+        context.set_font("10px Arial");
+        context.set_fill_style_str("#888888");
+        context.set_text_align("right");
+        context
+            .fill_text(footer, width - 5.0, height - 2.0)
+            .unwrap_or_else(|_| log::warn!("Failed to draw text"));
+        context.set_text_align("start");
+    }
+}
+
+/// Strokes a single vertical grid line from `top` to `bottom` at `x`.
+fn draw_vertical_line(
+    context: &CanvasRenderingContext2d,
+    x: f64,
+    top: f64,
+    bottom: f64,
+    props: &ChartProps,
+) {
+    context.begin_path();
+    context.move_to(x, top);
+    context.line_to(x, bottom);
+
+    context.set_stroke_style_str(&props.grid_color);
+    context.stroke();
+}
+
+/// Returns the hours at which the grid draws a vertical line, per `ChartProps::grid_mode`.
+///
+/// # Parameters
+/// - `segments`: A slice of `Segment` structs, used by `GridMode::TransitionsOnly`.
+/// - `props`: The chart properties holding the grid mode and time axis.
+///
+/// # Returns
+/// - `Vec<f32>`: Every whole hour on the axis with `GridMode::Hourly`, the transition
+///   hours on the axis with `GridMode::TransitionsOnly`, and none with `GridMode::None`.
+pub(crate) fn vertical_grid_hours(segments: &[Segment], props: &ChartProps) -> Vec<f32> {
+    let (axis_start, axis_end) = axis_range(props);
+    match props.grid_mode {
+        GridMode::Hourly => generate_hour_labels(props)
+            .into_iter()
+            .map(|(hour, _)| hour as f32)
+            .collect(),
+        GridMode::TransitionsOnly => transition_hours(segments)
+            .into_iter()
+            .filter(|hour| (axis_start..=axis_end).contains(hour))
+            .collect(),
+        GridMode::None => Vec::new(),
+    }
+}
+
+/// Collects the unique hours at which a segment starts or ends.
+///
+/// # Parameters
+/// - `segments`: A slice of `Segment` structs.
+///
+/// # Returns
+/// - `Vec<f32>`: The boundary hours, sorted ascending and without duplicates.
+fn transition_hours(segments: &[Segment]) -> Vec<f32> {
+    let mut hours: Vec<f32> = segments
+        .iter()
+        .flat_map(|segment| [segment.start_hour, segment.end_hour])
+        .collect();
+    hours.sort_by(f32::total_cmp);
+    hours.dedup();
+    hours
+}
+
+/// Finds the status changes joined by a vertical step on a paper-style log.
+///
+/// A step joins two consecutive segments when the first ends exactly where the second
+/// starts and they sit on different status rows. Segments separated by a gap, or that
+/// share a row, are not joined.
+///
+/// # Parameters
+/// - `segments`: A slice of `Segment` structs.
+///
+/// # Returns
+/// - `Vec<(&Segment, &Segment)>`: Each joined pair, in chronological order.
+pub(crate) fn status_steps(segments: &[Segment]) -> Vec<(&Segment, &Segment)> {
+    let mut sorted: Vec<&Segment> = segments.iter().collect();
+    sorted.sort_by(|a, b| a.start_hour.total_cmp(&b.start_hour));
+    sorted
+        .windows(2)
+        .map(|pair| (pair[0], pair[1]))
+        .filter(|(from, to)| {
+            from.end_hour == to.start_hour && status_row(&from.status) != status_row(&to.status)
+        })
+        .collect()
+}
+
+/// A drawing primitive for one piece of a duty status segment.
+///
+/// Produced by `segment_rects` without touching the DOM, so the chart's geometry can
+/// be checked on any target. Coordinates are in CSS pixels from the chart's left edge,
+/// before the offset `ChartProps::align` adds.
+///
+/// - `Line`: A line from `(x1, y1)` to `(x2, y2)`, used for segments and the vertical
+///   steps between them.
+/// - `Pill`: A rectangle with fully rounded ends, for `SegmentStyle::Pill`.
+/// - `Rect`: A rectangle, for `ChartStyle::Ribbon`.
+///
+/// `dashed` marks the sub-statuses (personal conveyance and yard move), which share a
+/// row with their parent status.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DrawCommand {
+    Line {
+        x1: f64,
+        y1: f64,
+        x2: f64,
+        y2: f64,
+        color: String,
+        dashed: bool,
+    },
+    Pill {
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+        color: String,
+        dashed: bool,
+    },
+    Rect {
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+        color: String,
+        dashed: bool,
+    },
+}
+
+/// Computes the shapes `draw_chart` draws for the segments, without a canvas.
+///
+/// The log is laid out exactly as `draw_chart` does: invalid segments are dropped, the
+/// day is completed with `end_of_day_status`, and the time axis is fitted to it. The
+/// result follows `width`, `height`, `max_width`, `style`, `segment_style`,
+/// `on_overflow`, `visible_range`, `min_segment_px`, and `highlight_overage`.
+///
+/// # Parameters
+/// - `segments`: A slice of `Segment` structs representing the driver's log.
+/// - `props`: A reference to `ChartProps` defining the chart's visual settings.
+///
+/// # Returns
+/// - `Vec<DrawCommand>`: The shapes, in drawing order.
+pub fn segment_rects(segments: &[Segment], props: &ChartProps) -> Vec<DrawCommand> {
+    let width = content_width(props.width as f64, props);
+    let (segments, layout) = lay_out(sanitize_segments(segments), props);
+    segment_commands(&segments, width, props.height as f64, &layout)
+}
+
+/// Draws the duty status segments on the chart.
+///
+/// With `ChartStyle::GridLines`, each segment is represented as a colored line in
+/// the row of the driver's status, and contiguous status changes are joined by a
+/// vertical step (see `status_steps`) as on a paper log. With `ChartStyle::Ribbon`,
+/// each segment fills the full height of the grid, forming a timeline ribbon.
+///
+/// # Parameters
+/// - `context`: The 2D rendering context.
+/// - `segments`: A slice of `Segment` structs.
+/// - `width`: The width of the canvas.
+/// - `height`: The height of the canvas.
+/// - `props`: The chart properties defining colors and styles.
+fn draw_segments(
+    context: &CanvasRenderingContext2d,
+    segments: &[Segment],
+    width: f64,
+    height: f64,
+    props: &ChartProps,
+) {
+    context.set_line_width(4.0);
+    for command in segment_commands(segments, width, height, props) {
+        draw_command(context, &command, props);
+    }
+}
+
+/// Computes the shapes of laid-out segments, as drawn by `draw_segments`.
+///
+/// # Parameters
+/// - `segments`: The segments, after `lay_out`.
+/// - `width`: The width of the chart.
+/// - `height`: The height of the canvas.
+/// - `props`: The chart properties returned by `lay_out`.
+///
+/// # Returns
+/// - `Vec<DrawCommand>`: The vertical steps first, then each segment's shape followed
+///   by its overage highlight, if any.
+fn segment_commands(
+    segments: &[Segment],
+    width: f64,
+    height: f64,
+    props: &ChartProps,
+) -> Vec<DrawCommand> {
+    let mut commands = Vec::new();
+    let overage_start = props
+        .highlight_overage
+        .then(|| driving_limit_reached_at(segments))
+        .flatten();
+
+    if props.style == ChartStyle::GridLines && props.segment_style == SegmentStyle::Line {
+        let (axis_start, axis_end) = axis_range(props);
+        for (from, to) in status_steps(segments) {
+            if !(axis_start..=axis_end).contains(&to.start_hour) {
+                continue;
+            }
+            let x = hour_to_x(to.start_hour, width, props);
+            if let Some((visible_start, visible_end)) = props.visible_range {
+                if x < visible_start || x > visible_end {
+                    continue;
+                }
+            }
+            commands.push(DrawCommand::Line {
+                x1: x,
+                y1: status_y(&from.status, height, props),
+                x2: x,
+                y2: status_y(&to.status, height, props),
+                color: props.color_for(&to.status).to_string(),
+                dashed: false,
+            });
+        }
+    }
+
+    for segment in segments {
+        let Some(segment) = clip_to_axis(segment, props) else {
+            continue;
+        };
+        let segment = &segment;
+        let y_val = status_y(&segment.status, height, props);
+        let (x_start, x_end) = segment_x_range(segment, width, props);
+
+        if let Some((visible_start, visible_end)) = props.visible_range {
+            if x_end < visible_start || x_start > visible_end {
+                continue;
+            }
+        }
+
+        let color = props.color_for(&segment.status);
+        let dashed = is_sub_status(&segment.status);
+        commands.push(segment_shape(
+            x_start, x_end, y_val, color, dashed, height, props,
+        ));
+
+        if let Some(limit_hour) = overage_start {
+            if segment.status == DutyStatus::Driving && segment.end_hour > limit_hour {
+                let x_over = hour_to_x(segment.start_hour.max(limit_hour), width, props);
+                commands.push(segment_shape(
+                    x_over,
+                    x_end,
+                    y_val,
+                    &props.overage_color,
+                    false,
+                    height,
+                    props,
+                ));
+            }
+        }
+    }
+    commands
+}
+
+/// Returns one segment's shape between `x_start` and `x_end` according to the chart style.
+///
+/// # Parameters
+/// - `x_start`: The x-coordinate where the shape begins.
+/// - `x_end`: The x-coordinate where the shape ends.
+/// - `y_val`: The y-coordinate of the center of the segment's status row.
+/// - `color`: The color to stroke or fill the shape with.
+/// - `dashed`: Whether the shape's outline is dashed.
+/// - `height`: The height of the canvas.
+/// - `props`: The chart properties defining the styles.
+fn segment_shape(
+    x_start: f64,
+    x_end: f64,
+    y_val: f64,
+    color: &str,
+    dashed: bool,
+    height: f64,
+    props: &ChartProps,
+) -> DrawCommand {
+    let color = color.to_string();
+    match props.style {
+        ChartStyle::GridLines => match props.segment_style {
+            SegmentStyle::Line => DrawCommand::Line {
+                x1: x_start,
+                y1: y_val,
+                x2: x_end,
+                y2: y_val,
+                color,
+                dashed,
+            },
+            SegmentStyle::Pill => DrawCommand::Pill {
+                x: x_start,
+                y: y_val - props.pill_height / 2.0,
+                width: x_end - x_start,
+                height: props.pill_height,
+                color,
+                dashed,
+            },
+        },
+        ChartStyle::Ribbon => {
+            let (top, bottom) = (row_y(0, height, props), row_y(4, height, props));
+            DrawCommand::Rect {
+                x: x_start,
+                y: top,
+                width: x_end - x_start,
+                height: bottom - top,
+                color,
+                dashed,
+            }
+        }
+    }
+}
+
+/// Issues the canvas calls for one drawing primitive.
+///
+/// Lines are stroked; pills and rectangles are filled, then outlined with
+/// `ChartProps::segment_border`, if one is set.
+///
+/// # Parameters
+/// - `context`: The 2D rendering context.
+/// - `command`: The primitive to draw.
+/// - `props`: The chart properties holding the segment border.
+fn draw_command(context: &CanvasRenderingContext2d, command: &DrawCommand, props: &ChartProps) {
+    let dashed = match command {
+        DrawCommand::Line { dashed, .. }
+        | DrawCommand::Pill { dashed, .. }
+        | DrawCommand::Rect { dashed, .. } => *dashed,
+    };
+    if dashed {
+        set_line_dash(context, &[6.0, 4.0]);
+    }
+    match command {
+        DrawCommand::Line {
+            x1,
+            y1,
+            x2,
+            y2,
+            color,
+            ..
+        } => {
+            context.set_stroke_style_str(color);
+            context.begin_path();
+            context.move_to(*x1, *y1);
+            context.line_to(*x2, *y2);
+            context.stroke();
+        }
+        DrawCommand::Pill {
+            x,
+            y,
+            width,
+            height,
+            color,
+            ..
+        } => {
+            context.set_fill_style_str(color);
+            context.begin_path();
+            context
+                .round_rect_with_f64(*x, *y, *width, *height, height / 2.0)
+                .unwrap_or_else(|_| log::warn!("Failed to build pill path"));
+            context.fill();
+            stroke_segment_border(context, props);
+        }
+        DrawCommand::Rect {
+            x,
+            y,
+            width,
+            height,
+            color,
+            ..
+        } => {
+            context.set_fill_style_str(color);
+            context.begin_path();
+            context.rect(*x, *y, *width, *height);
+            context.fill();
+            stroke_segment_border(context, props);
+        }
+    }
+    if dashed {
+        set_line_dash(context, &[]);
+    }
+}
+
+/// Strokes the current path with `ChartProps::segment_border`, if one is set.
+fn stroke_segment_border(context: &CanvasRenderingContext2d, props: &ChartProps) {
+    if let Some((color, line_width)) = &props.segment_border {
+        context.save();
+        context.set_stroke_style_str(color);
+        context.set_line_width(*line_width);
+        context.stroke();
+        context.restore();
+    }
+}
+
+/// Sets the canvas line dash pattern; an empty pattern draws solid lines.
+fn set_line_dash(context: &CanvasRenderingContext2d, pattern: &[f64]) {
+    let segments: Array = pattern
+        .iter()
+        .map(|&length| JsValue::from(length))
+        .collect();
+    context
+        .set_line_dash(&segments)
+        .unwrap_or_else(|_| log::warn!("Failed to set line dash"));
+}
+
+/// Links each split-sleeper pair with a bracket drawn just below the grid.
+///
+/// The bracket spans from the middle of the first rest period to the middle of
+/// the second one.
+///
+/// # Parameters
+/// - `context`: The 2D rendering context.
+/// - `segments`: A slice of `Segment` structs.
+/// - `width`: The width of the canvas.
+/// - `height`: The height of the canvas.
+/// - `props`: The chart properties defining colors and styles.
+fn draw_split_sleeper_brackets(
+    context: &CanvasRenderingContext2d,
+    segments: &[Segment],
+    width: f64,
+    height: f64,
+    props: &ChartProps,
+) {
+    let top = row_y(4, height, props) + 4.0;
+    let bottom = top + 6.0;
+    let (axis_start, axis_end) = axis_range(props);
+    let middle = |segment: &Segment| {
+        let hour = (segment.start_hour + segment.end_hour) / 2.0;
+        hour_to_x(hour.clamp(axis_start, axis_end), width, props)
+    };
+
+    context.set_line_width(1.0);
+    context.set_stroke_style_str(&props.sleeper_color);
+
+    for (first, second) in find_split_sleeper_pairs(segments) {
+        let (x_first, x_second) = (middle(&segments[first]), middle(&segments[second]));
+        context.begin_path();
+        context.move_to(x_first, top);
+        context.line_to(x_first, bottom);
+        context.line_to(x_second, bottom);
+        context.line_to(x_second, top);
+        context.stroke();
+    }
+}
+
+/// Draws a vertical line labeled "NOW" across the four rows at the current hour.
+///
+/// Nothing is drawn when `hour` is outside the time axis.
+///
+/// # Parameters
+/// - `context`: The 2D rendering context.
+/// - `hour`: The current hour of the day.
+/// - `width`: The width of the canvas.
+/// - `height`: The height of the canvas.
+/// - `props`: The chart properties holding `now_color`.
+fn draw_now_marker(
+    context: &CanvasRenderingContext2d,
+    hour: f32,
+    width: f64,
+    height: f64,
+    props: &ChartProps,
+) {
+    let (axis_start, axis_end) = axis_range(props);
+    if !(axis_start..=axis_end).contains(&hour) {
+        return;
+    }
+    let x = hour_to_x(hour, width, props);
+    let top = row_y(0, height, props);
+
+    context.set_line_width(2.0);
+    context.set_stroke_style_str(&props.now_color);
+    context.begin_path();
+    context.move_to(x, top);
+    context.line_to(x, row_y(4, height, props));
+    context.stroke();
+
+    context.set_fill_style_str(&props.now_color);
+    context.set_font("bold 10px Arial");
+    context.set_text_align("center");
+    context
+        .fill_text("NOW", x, top - NOW_LABEL_OFFSET)
+        .unwrap_or_else(|_| log::warn!("Failed to draw text"));
+    context.set_text_align("start");
+}
+
+/// Distance, in pixels, from the top of the grid to the "NOW" label baseline.
+pub(crate) const NOW_LABEL_OFFSET: f64 = 4.0;
+
+/// Draws the odometer reading under the tick of each status change that records one.
+///
+/// # Parameters
+/// - `context`: The 2D rendering context.
+/// - `segments`: A slice of `Segment` structs.
+/// - `width`: The width of the canvas.
+/// - `height`: The height of the canvas.
+/// - `props`: The chart properties defining colors and styles.
+fn draw_odometer_readings(
+    context: &CanvasRenderingContext2d,
+    segments: &[Segment],
+    width: f64,
+    height: f64,
+    props: &ChartProps,
+) {
+    let bottom = row_y(4, height, props);
+
+    context.set_line_width(1.0);
+    context.set_stroke_style_str(&props.grid_color);
+    context.set_fill_style_str(&props.label_color);
+    context.set_font("10px Arial");
+    context.set_text_align("center");
+
+    for (hour, reading) in odometer_readings(segments, props) {
+        let x = hour_to_x(hour, width, props);
+        context.begin_path();
+        context.move_to(x, bottom);
+        context.line_to(x, bottom + ODOMETER_TICK_LENGTH);
+        context.stroke();
+        context
+            .fill_text(&reading, x, bottom + ODOMETER_TEXT_OFFSET)
+            .unwrap_or_else(|_| log::warn!("Failed to draw text"));
+    }
+    context.set_text_align("start");
+}
+
+/// Length, in pixels, of the tick drawn below the grid at an odometer reading.
+pub(crate) const ODOMETER_TICK_LENGTH: f64 = 4.0;
+
+/// Distance, in pixels, from the bottom of the grid to the odometer text baseline.
+pub(crate) const ODOMETER_TEXT_OFFSET: f64 = 16.0;
+
+/// Lists the start hour and formatted reading of each segment with an odometer reading
+/// on the time axis.
+pub(crate) fn odometer_readings(segments: &[Segment], props: &ChartProps) -> Vec<(f32, String)> {
+    let (axis_start, axis_end) = axis_range(props);
+    segments
+        .iter()
+        .filter(|segment| (axis_start..=axis_end).contains(&segment.start_hour))
+        .filter_map(|segment| Some((segment.start_hour, format!("{:.0}", segment.odometer?))))
+        .collect()
+}
+
+/// Draws the side panel with a swatch, label, and total hours per duty status.
+///
+/// The panel is cleared and redrawn on every call, since the totals change with the
+/// segments even when the grid is cached.
+///
+/// # Parameters
+/// - `context`: The 2D rendering context.
+/// - `segments`: A slice of `Segment` structs to total.
+/// - `width`: The width of the canvas.
+/// - `height`: The height of the canvas.
+/// - `props`: The chart properties holding the status colors.
+fn draw_side_panel(
+    context: &CanvasRenderingContext2d,
+    segments: &[Segment],
+    width: f64,
+    height: f64,
+    props: &ChartProps,
+) {
+    let left = grid_right(width, props);
+    let top = row_y(0, height, props);
+    context.clear_rect(left, top - 15.0, SIDE_PANEL_WIDTH, height - top);
+    context.set_font("12px Arial");
+
+    let totals = status_totals(segments);
+    for (i, (status, color)) in legend_entries(segments, props).into_iter().enumerate() {
+        let y = top + i as f64 * SIDE_PANEL_LINE_HEIGHT;
+        context.set_fill_style_str(color);
+        context.fill_rect(left + 10.0, y - 10.0, 10.0, 10.0);
+
+        context.set_fill_style_str(&props.label_color);
+        context
+            .fill_text(status_label(&status), left + 26.0, y)
+            .unwrap_or_else(|_| log::warn!("Failed to draw text"));
+        context.set_text_align("right");
+        context
+            .fill_text(&format!("{:.1}h", totals[status.index()]), width - 10.0, y)
+            .unwrap_or_else(|_| log::warn!("Failed to draw text"));
+        context.set_text_align("start");
+    }
+}
+
+/// Vertical distance in pixels between the lines of the side panel.
+pub(crate) const SIDE_PANEL_LINE_HEIGHT: f64 = 18.0;
+
+/// Returns the human-readable label of a duty status, e.g. `"Off Duty"`.
+pub(crate) fn status_label(status: &DutyStatus) -> &'static str {
+    match status {
+        DutyStatus::OffDuty => "Off Duty",
+        DutyStatus::Sleeper => "Sleeper",
+        DutyStatus::Driving => "Driving",
+        DutyStatus::OnDuty => "On Duty",
+        DutyStatus::PersonalConveyance => "Personal Conv.",
+        DutyStatus::YardMove => "Yard Move",
+    }
+}
+
+/// Draws the annotation markers and their labels.
+///
+/// Each annotation is drawn as a filled circle at its `(hour, status)` position,
+/// with the label written just above and to the right of the marker.
+///
+/// # Parameters
+/// - `context`: The 2D rendering context.
+/// - `width`: The width of the canvas.
+/// - `height`: The height of the canvas.
+/// - `props`: The chart properties holding the annotations.
+fn draw_annotations(
+    context: &CanvasRenderingContext2d,
+    width: f64,
+    height: f64,
+    props: &ChartProps,
+) {
+    context.set_font("12px Arial");
+
+    let (axis_start, axis_end) = axis_range(props);
+    for annotation in &props.annotations {
+        if !(axis_start..=axis_end).contains(&annotation.hour) {
+            continue;
+        }
+        let y = status_y(&annotation.status, height, props);
+        let x = hour_to_x(annotation.hour, width, props);
+
+        context.set_fill_style_str(&annotation.color);
+        context.begin_path();
+        context
+            .arc(x, y, 5.0, 0.0, std::f64::consts::TAU)
+            .unwrap_or_else(|_| log::warn!("Failed to draw annotation marker"));
+        context.fill();
+        context
+            .fill_text(&annotation.label, x + 8.0, y - 8.0)
+            .unwrap_or_else(|_| log::warn!("Failed to draw text"));
+    }
+}
+
+/// Hour-label font size, in pixels, per pixel of hour column width.
+const HOUR_LABEL_FONT_RATIO: f64 = 0.375;
+
+/// Smallest hour-label font size, in pixels.
+const MIN_HOUR_LABEL_FONT_PX: f64 = 10.0;
+
+/// Largest hour-label font size, in pixels.
+const MAX_HOUR_LABEL_FONT_PX: f64 = 24.0;
+
+/// Computes the font size of the hour labels, which grows with the hour columns.
+///
+/// The size is proportional to the column width, rounded to a whole pixel and kept
+/// between 10 and 24 pixels, so the default 900-pixel chart keeps 12-pixel labels.
+///
+/// # Parameters
+/// - `width`: The width of the canvas.
+/// - `props`: The chart properties, used for the time axis.
+///
+/// # Returns
+/// - `f64`: The font size in pixels.
+pub(crate) fn hour_label_font_px(width: f64, props: &ChartProps) -> f64 {
+    let (axis_start, axis_end) = axis_range(props);
+    let col_width = (grid_right(width, props) - 2.0 * PADDING_X) / (axis_end - axis_start) as f64;
+    (col_width * HOUR_LABEL_FONT_RATIO)
+        .round()
+        .clamp(MIN_HOUR_LABEL_FONT_PX, MAX_HOUR_LABEL_FONT_PX)
+}
+
+/// Returns how far left of its hour line an hour label starts, for a font size.
+pub(crate) fn hour_label_offset(font_px: f64) -> f64 {
+    font_px * 10.0 / 12.0
+}
+
+/// Horizontal space, in pixels, reserved for the status labels and the right margin.
+pub(crate) const PADDING_X: f64 = 70.0;
+
+/// Vertical space, in pixels, reserved above and below the status rows.
+pub(crate) const PADDING_Y: f64 = 40.0;
+
+/// Converts an hour of the day into a canvas x-coordinate.
+///
+/// With `ChartProps::integer_layout`, the exact position is rounded to a whole pixel.
+/// Rounding each position independently spreads the rounding error across columns,
+/// so hour 24 still lands exactly at `width - PADDING_X`.
+///
+/// # Parameters
+/// - `hour`: The hour to convert (in 24-hour format).
+/// - `width`: The width of the canvas.
+/// - `props`: The chart properties, used for the layout mode.
+///
+/// # Returns
+/// - `f64`: The x-coordinate of the hour on the time axis.
+pub(crate) fn hour_to_x(hour: f32, width: f64, props: &ChartProps) -> f64 {
+    let (axis_start, axis_end) = axis_range(props);
+    let col_width = (grid_right(width, props) - 2.0 * PADDING_X) / (axis_end - axis_start) as f64;
+    snap(PADDING_X + (hour - axis_start) as f64 * col_width, props)
+}
+
+/// Returns the hours at the left and right edges of the time axis.
+///
+/// The axis spans `start_hour` to `end_hour`, where an `end_hour` of `24.0` stands for
+/// the end of `hours_span`. Both are kept within `0.0..=hours_span`, and an empty
+/// range falls back to the whole span.
+pub(crate) fn axis_range(props: &ChartProps) -> (f32, f32) {
+    let end = if props.end_hour == 24.0 {
+        props.hours_span
+    } else {
+        props.end_hour.min(props.hours_span)
+    };
+    let start = props.start_hour.max(0.0);
+    if start < end {
+        (start, end)
+    } else {
+        (0.0, props.hours_span)
+    }
+}
+
+/// Clips a segment to the time axis, or returns `None` if it lies entirely outside.
+pub(crate) fn clip_to_axis(segment: &Segment, props: &ChartProps) -> Option<Segment> {
+    let (axis_start, axis_end) = axis_range(props);
+    let start_hour = segment.start_hour.max(axis_start);
+    let end_hour = segment.end_hour.min(axis_end);
+    (start_hour < end_hour).then(|| Segment {
+        start_hour,
+        end_hour,
+        ..segment.clone()
+    })
+}
+
+/// Computes the x-coordinates a segment is drawn between.
+///
+/// Segments narrower than `ChartProps::min_segment_px` are widened around their center.
+///
+/// # Parameters
+/// - `segment`: The segment to place.
+/// - `width`: The width of the canvas.
+/// - `props`: The chart properties, used for the layout mode.
+///
+/// # Returns
+/// - `(f64, f64)`: The starting and ending x-coordinates.
+pub(crate) fn segment_x_range(segment: &Segment, width: f64, props: &ChartProps) -> (f64, f64) {
+    let x_start = hour_to_x(segment.start_hour, width, props);
+    let x_end = hour_to_x(segment.end_hour, width, props);
+    if x_end - x_start >= props.min_segment_px {
+        return (x_start, x_end);
+    }
+    let center = (x_start + x_end) / 2.0;
+    (
+        center - props.min_segment_px / 2.0,
+        center + props.min_segment_px / 2.0,
+    )
+}
+
+/// Width in pixels reserved for the side panel when `ChartProps::side_panel` is set.
+pub(crate) const SIDE_PANEL_WIDTH: f64 = 170.0;
+
+/// Returns the x-coordinate where the grid ends, leaving room for the side panel.
+pub(crate) fn grid_right(width: f64, props: &ChartProps) -> f64 {
+    if props.side_panel {
+        width - SIDE_PANEL_WIDTH
+    } else {
+        width
+    }
+}
+
+/// Computes the y-coordinate of the horizontal line above a display row.
+///
+/// Row `4` is the bottom line of the last row. Rows share the available height
+/// according to `ChartProps::row_heights`, or equally when it is `None`.
+///
+/// # Parameters
+/// - `row`: The display row, from `0` (top) to `4` (bottom line).
+/// - `height`: The height of the canvas.
+/// - `props`: The chart properties, used for the layout mode.
+///
+/// # Returns
+/// - `f64`: The y-coordinate of the line.
+pub(crate) fn row_y(row: usize, height: f64, props: &ChartProps) -> f64 {
+    let weights = props.row_heights.unwrap_or([1.0; 4]);
+    let total: f64 = weights.iter().sum();
+    let above: f64 = (0..row).map(|r| weights[display_row(r, props)]).sum();
+    snap(
+        PADDING_Y + above / total * (height - 2.0 * PADDING_Y),
+        props,
+    )
+}
+
+/// Rounds a coordinate to a whole pixel when `ChartProps::integer_layout` is set.
+fn snap(value: f64, props: &ChartProps) -> f64 {
+    if props.integer_layout {
+        value.round()
+    } else {
+        value
+    }
+}
+
+/// Computes the y-coordinate of the center of a status row.
+///
+/// # Parameters
+/// - `status`: The duty status whose row is requested.
+/// - `height`: The height of the canvas.
+/// - `props`: The chart properties, used for the row order.
+///
+/// # Returns
+/// - `f64`: The y-coordinate of the row center.
+pub(crate) fn status_y(status: &DutyStatus, height: f64, props: &ChartProps) -> f64 {
+    let row = display_row(status_row(status), props);
+    let center = (row_y(row, height, props) + row_y(row + 1, height, props)) / 2.0;
+    snap(center, props)
+}
+
+/// Computes the y-coordinate of one team driver's line within a status row.
+///
+/// Driver A is drawn a third of the way down the row and driver B two thirds down.
+///
+/// # Parameters
+/// - `status`: The duty status whose row is requested.
+/// - `second_driver`: Whether the line belongs to driver B.
+/// - `height`: The height of the canvas.
+/// - `props`: The chart properties, used for the row order.
+///
+/// # Returns
+/// - `f64`: The y-coordinate of the driver's line.
+pub(crate) fn team_y(
+    status: &DutyStatus,
+    second_driver: bool,
+    height: f64,
+    props: &ChartProps,
+) -> f64 {
+    let row = display_row(status_row(status), props);
+    let (top, bottom) = (row_y(row, height, props), row_y(row + 1, height, props));
+    let fraction = if second_driver { 2.0 / 3.0 } else { 1.0 / 3.0 };
+    snap(top + (bottom - top) * fraction, props)
+}
+
+/// Lists every duty status with the color it is drawn in.
+///
+/// Useful to build a legend outside the chart that stays in sync with `ChartProps`.
+///
+/// # Parameters
+/// - `props`: The chart properties holding the status colors.
+///
+/// # Returns
+/// - `Vec<(DutyStatus, &str)>`: Six status/color pairs, in the order of `DutyStatus::ALL`.
+pub fn status_color_pairs(props: &ChartProps) -> Vec<(DutyStatus, &str)> {
+    DutyStatus::ALL
+        .into_iter()
+        .map(|status| {
+            let color = props.color_for(&status);
+            (status, color)
+        })
+        .collect()
+}
+
+/// Lists the status/color pairs shown in the legend.
+///
+/// Every status is listed unless `ChartProps::legend_active_only` is set, in which
+/// case only the statuses appearing in `segments` are.
+pub(crate) fn legend_entries<'a>(
+    segments: &[Segment],
+    props: &'a ChartProps,
+) -> Vec<(DutyStatus, &'a str)> {
+    status_color_pairs(props)
+        .into_iter()
+        .filter(|(status, _)| {
+            !props.legend_active_only || segments.iter().any(|segment| &segment.status == status)
+        })
+        .collect()
+}
+
+/// Returns the logical row of a duty status (0 = Off Duty, ..., 3 = On Duty).
+///
+/// FMCSA treats Personal Conveyance as a kind of Off Duty time and Yard Move as a kind
+/// of On Duty time, so they share those rows and are told apart by `is_sub_status`.
+fn status_row(status: &DutyStatus) -> usize {
+    match status {
+        DutyStatus::OffDuty | DutyStatus::PersonalConveyance => 0,
+        DutyStatus::Sleeper => 1,
+        DutyStatus::Driving => 2,
+        DutyStatus::OnDuty | DutyStatus::YardMove => 3,
+    }
+}
+
+/// Returns the label drawn for a logical row, abbreviated when `compact_labels` is set.
+pub(crate) fn row_label(row: usize, props: &ChartProps) -> &'static str {
+    let status = &DutyStatus::ALL[row];
+    if props.compact_labels {
+        status.abbreviation()
+    } else {
+        status_label(status)
+    }
+}
+
+/// Returns whether a status shares another status's row and is drawn dashed.
+pub(crate) fn is_sub_status(status: &DutyStatus) -> bool {
+    matches!(
+        status,
+        DutyStatus::PersonalConveyance | DutyStatus::YardMove
+    )
+}
+
+/// Maps a logical row to the row it is drawn in, honoring `ChartProps::flip_y`.
+pub(crate) fn display_row(row: usize, props: &ChartProps) -> usize {
+    if props.flip_y {
+        3 - row
+    } else {
+        row
+    }
+}
+
+/// Generates a list of hour labels for the chart.
+///
+/// The function returns formatted hour labels in 12-hour AM/PM format, one per whole
+/// hour on the time axis (see `axis_range`), each paired with its hour. Hours past
+/// midnight wrap around, so a 24-hour span ranges from "12 AM" to "12 AM".
+///
+/// # Parameters
+/// - `props`: The chart properties holding the time axis range.
+///
+/// # Returns
+/// - `Vec<(u32, String)>`: Each whole hour on the axis and its label.
+pub(crate) fn generate_hour_labels(props: &ChartProps) -> Vec<(u32, String)> {
+    let (axis_start, axis_end) = axis_range(props);
+    (axis_start.ceil() as u32..=axis_end.floor() as u32)
+        .map(|hour| {
+            let h = hour % 24;
+            let label = format!(
+                "{} {}",
+                if h == 0 || h == 12 { 12 } else { h % 12 },
+                if h < 12 { "AM" } else { "PM" }
+            );
+            (hour, label)
+        })
+        .collect()
+}
+
+/// Describes a segment for a tooltip: its status and time range, then its location and
+/// note on their own lines when they are set.
+///
+/// # Parameters
+/// - `segment`: The segment to describe.
+///
+/// # Returns
+/// - `String`: The description, e.g. `"Driving, 6:00 AM - 11:30 AM\nDallas, TX"`.
+pub fn segment_tooltip(segment: &Segment) -> String {
+    let mut lines = vec![format!(
+        "{}, {} - {}",
+        status_label(&segment.status),
+        format_clock(segment.start_hour),
+        format_clock(segment.end_hour)
+    )];
+    lines.extend(
+        [&segment.location, &segment.note]
+            .into_iter()
+            .filter(|text| !text.is_empty())
+            .cloned(),
+    );
+    lines.join("\n")
+}
+
+/// Formats an hour of the day as a 12-hour clock time to the minute, e.g. `"1:30 PM"`.
+fn format_clock(hour: f32) -> String {
+    let minutes = (hour * 60.0).round() as u32;
+    let (h, m) = ((minutes / 60) % 24, minutes % 60);
+    format!(
+        "{}:{:02} {}",
+        if h == 0 || h == 12 { 12 } else { h % 12 },
+        m,
+        if h < 12 { "AM" } else { "PM" }
+    )
+}
+
+/// Strategy used by `merge_logs` when an incoming segment overlaps a base segment.
+///
+/// - `PreferBase`: Keep the base segment and trim the incoming one around it.
+/// - `PreferIncoming`: Keep the incoming segment and trim the base one around it.
+/// - `Error`: Refuse to merge and report the first conflict.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MergeStrategy {
+    PreferBase,
+    PreferIncoming,
+    Error,
+}
+
+/// Merges a batch of incoming segments into an existing log.
+///
+/// Non-overlapping segments from both logs are kept as-is. Where segments overlap,
+/// the losing side is trimmed (and split if needed) so that only the uncovered parts
+/// remain. Segments that merely touch (one ends where the other starts) do not conflict.
+///
+/// # Parameters
+/// - `base`: The existing log.
+/// - `incoming`: The segments to merge into the log.
+/// - `strategy`: How overlapping ranges are resolved.
+///
+/// # Returns
+/// - `Ok(Vec<Segment>)`: The merged log, sorted by `start_hour`.
+/// - `Err(String)`: With `MergeStrategy::Error`, if an incoming segment overlaps the base log.
+pub fn merge_logs(
+    base: &[Segment],
+    incoming: &[Segment],
+    strategy: MergeStrategy,
+) -> Result<Vec<Segment>, String> {
+    let mut merged = match strategy {
+        MergeStrategy::PreferBase => {
+            let mut merged = base.to_vec();
+            merged.extend(incoming.iter().flat_map(|segment| subtract(segment, base)));
+            merged
+        }
+        MergeStrategy::PreferIncoming => {
+            let mut merged: Vec<Segment> = base
+                .iter()
+                .flat_map(|segment| subtract(segment, incoming))
+                .collect();
+            merged.extend_from_slice(incoming);
+            merged
+        }
+        MergeStrategy::Error => {
+            for (i, segment) in incoming.iter().enumerate() {
+                if let Some(j) = base.iter().position(|other| overlaps(segment, other)) {
+                    return Err(format!(
+                        "Incoming segment {} overlaps existing segment {}",
+                        i, j
+                    ));
+                }
+            }
+            let mut merged = base.to_vec();
+            merged.extend_from_slice(incoming);
+            merged
+        }
+    };
+
+    merged.sort_by(|a, b| a.start_hour.total_cmp(&b.start_hour));
+    Ok(merged)
+}
+
+/// Returns whether two segments share any time (touching segments do not overlap).
+fn overlaps(a: &Segment, b: &Segment) -> bool {
+    a.start_hour < b.end_hour && b.start_hour < a.end_hour
+}
+
+/// Removes the time covered by `covers` from `segment`.
+///
+/// # Returns
+/// - `Vec<Segment>`: The remaining pieces of `segment`, which may be empty or split in two.
+fn subtract(segment: &Segment, covers: &[Segment]) -> Vec<Segment> {
+    let mut pieces = vec![segment.clone()];
+
+    for cover in covers {
+        pieces = pieces
+            .into_iter()
+            .flat_map(|piece| {
+                if !overlaps(&piece, cover) {
+                    return vec![piece];
+                }
+                let mut remaining = Vec::new();
+                if piece.start_hour < cover.start_hour {
+                    remaining.push(Segment {
+                        end_hour: cover.start_hour,
+                        ..piece.clone()
+                    });
+                }
+                if piece.end_hour > cover.end_hour {
+                    remaining.push(Segment {
+                        start_hour: cover.end_hour,
+                        ..piece
+                    });
+                }
+                remaining
+            })
+            .collect();
+    }
+
+    pieces
+}
+
+/// Finds the hour ranges where two versions of a log differ.
+///
+/// A segment that appears in only one of the logs, or changed in any field, marks its
+/// hours as changed. Overlapping and touching ranges are merged.
+///
+/// # Parameters
+/// - `previous`: The earlier version of the log.
+/// - `segments`: The newer version of the log.
+///
+/// # Returns
+/// - `Vec<(f32, f32)>`: The changed `(start_hour, end_hour)` ranges, sorted by start;
+///   empty if the logs hold the same segments.
+pub fn diff_logs(previous: &[Segment], segments: &[Segment]) -> Vec<(f32, f32)> {
+    let removed = previous
+        .iter()
+        .filter(|segment| !segments.contains(segment));
+    let added = segments
+        .iter()
+        .filter(|segment| !previous.contains(segment));
+    let mut ranges: Vec<(f32, f32)> = removed
+        .chain(added)
+        .map(|segment| (segment.start_hour, segment.end_hour))
+        .collect();
+    ranges.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let mut merged: Vec<(f32, f32)> = Vec::with_capacity(ranges.len());
+    for (start, end) in ranges {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+/// Largest distance, in hours, between one segment's end and the next one's start for
+/// `merge_adjacent` to treat them as contiguous (about 3.6 seconds).
+const CONTIGUITY_TOLERANCE_HOURS: f32 = 0.001;
+
+/// Coalesces contiguous segments that share the same duty status.
+///
+/// After sorting by `start_hour`, a segment starting within a few seconds of where the
+/// previous one ends, in the same status, is folded into it. The merged segment keeps
+/// the first location and odometer reading, and joins the distinct non-empty notes
+/// with `"; "`.
+///
+/// # Parameters
+/// - `segments`: A slice of `Segment` structs representing the driver's log.
+///
+/// # Returns
+/// - `Vec<Segment>`: The merged log, sorted by `start_hour`.
+pub fn merge_adjacent(segments: &[Segment]) -> Vec<Segment> {
+    let mut sorted = segments.to_vec();
+    sorted.sort_by(|a, b| a.start_hour.total_cmp(&b.start_hour));
+
+    let mut merged: Vec<Segment> = Vec::with_capacity(sorted.len());
+    for segment in sorted {
+        match merged.last_mut() {
+            Some(last)
+                if last.status == segment.status
+                    && (segment.start_hour - last.end_hour).abs() <= CONTIGUITY_TOLERANCE_HOURS =>
+            {
+                last.end_hour = last.end_hour.max(segment.end_hour);
+                if !segment.note.is_empty() && !last.note.split("; ").any(|n| n == segment.note) {
+                    if !last.note.is_empty() {
+                        last.note.push_str("; ");
+                    }
+                    last.note.push_str(&segment.note);
+                }
+            }
+            _ => merged.push(segment),
+        }
+    }
+    merged
+}
+
+/// Snaps a newly entered segment to the nearest existing boundaries.
+///
+/// Each end of `new_seg` moves to the closest `start_hour` or `end_hour` of the
+/// existing segments when that boundary is within `tolerance` hours, so a segment
+/// entered as ending at 12.02 next to one starting at 12.0 ends up touching it.
+///
+/// # Parameters
+/// - `new_seg`: The segment being added.
+/// - `existing`: The segments already in the log.
+/// - `tolerance`: The largest distance, in hours, a boundary may be moved.
+///
+/// # Returns
+/// - `Segment`: A copy of `new_seg` with its start and end snapped where possible.
+pub fn snap_to_neighbors(new_seg: &Segment, existing: &[Segment], tolerance: f32) -> Segment {
+    let snap_hour = |hour: f32| {
+        existing
+            .iter()
+            .flat_map(|segment| [segment.start_hour, segment.end_hour])
+            .filter(|boundary| (boundary - hour).abs() <= tolerance)
+            .min_by(|a, b| (a - hour).abs().total_cmp(&(b - hour).abs()))
+            .unwrap_or(hour)
+    };
+    Segment {
+        start_hour: snap_hour(new_seg.start_hour),
+        end_hour: snap_hour(new_seg.end_hour),
+        ..new_seg.clone()
+    }
+}
+
+/// Sums the hours logged in each duty status.
+///
+/// # Parameters
+/// - `segments`: A slice of `Segment` structs representing the driver's log.
+///
+/// # Returns
+/// - `[f32; 6]`: The hours per status, in the order of `DutyStatus::ALL`.
+pub fn status_totals(segments: &[Segment]) -> [f32; 6] {
+    segments.iter().fold([0.0; 6], |mut totals, segment| {
+        totals[segment.status.index()] += segment.end_hour - segment.start_hour;
+        totals
+    })
+}
+
+/// Finds the longest single segment logged in each duty status.
+///
+/// Unlike `status_totals`, segments of the same status are not added together.
+///
+/// # Parameters
+/// - `segments`: A slice of `Segment` structs representing the driver's log.
+///
+/// # Returns
+/// - `[f32; 6]`: The longest duration per status, in the order of `DutyStatus::ALL`;
+///   `0.0` for a status with no segment.
+pub fn longest_segment_per_status(segments: &[Segment]) -> [f32; 6] {
+    segments.iter().fold([0.0; 6], |mut longest, segment| {
+        let longest_for_status = &mut longest[segment.status.index()];
+        *longest_for_status = longest_for_status.max(segment.end_hour - segment.start_hour);
+        longest
+    })
+}
+
+/// Returns the time-weighted center of a status, e.g. the hour around which a driver
+/// mostly drives.
+///
+/// Each segment contributes its midpoint hour, weighted by its duration.
+///
+/// # Parameters
+/// - `segments`: A slice of `Segment` structs representing the driver's log.
+/// - `status`: The duty status to locate.
+///
+/// # Returns
+/// - `Some(f32)`: The duration-weighted average midpoint hour of the status.
+/// - `None`: If no segment with a positive duration has that status.
+pub fn status_centroid(segments: &[Segment], status: &DutyStatus) -> Option<f32> {
+    let (weighted_hours, total_hours) = segments
+        .iter()
+        .filter(|segment| segment.status == *status)
+        .fold((0.0, 0.0), |(weighted, total), segment| {
+            let duration = segment.end_hour - segment.start_hour;
+            let midpoint = (segment.start_hour + segment.end_hour) / 2.0;
+            (weighted + midpoint * duration, total + duration)
+        });
+    (total_hours > 0.0).then(|| weighted_hours / total_hours)
+}
+
+/// Returns the duty status in effect at a given hour.
+///
+/// Segments cover the half-open range `[start_hour, end_hour)`, so at a transition the
+/// new status applies. The end of the day is the exception: a segment ending at `24.0`
+/// also covers hour `24.0`, just as it is drawn up to the right edge of the grid and
+/// leaves no trailing gap in `find_gaps`.
+///
+/// # Parameters
+/// - `segments`: A slice of `Segment` structs representing the driver's log.
+/// - `hour`: The hour to look up (in 24-hour format).
+///
+/// # Returns
+/// - `Some(DutyStatus)`: The status of the segment covering `hour`.
+/// - `None`: If no segment covers it.
+pub fn status_at(segments: &[Segment], hour: f32) -> Option<DutyStatus> {
+    segments
+        .iter()
+        .find(|segment| {
+            segment.start_hour <= hour
+                && (hour < segment.end_hour || (hour == 24.0 && segment.end_hour == 24.0))
+        })
+        .map(|segment| segment.status.clone())
+}
+
+/// Samples the day's duty status on a fixed grid of 96 fifteen-minute slots.
+///
+/// Each slot takes the status that covers most of it, so a slot straddling a transition
+/// gets the dominant status; ties go to the status listed first in `DutyStatus::ALL`.
+/// Slots no segment touches are `None`.
+///
+/// # Parameters
+/// - `segments`: A slice of `Segment` structs representing the driver's log.
+///
+/// # Returns
+/// - `[Option<DutyStatus>; 96]`: The status of each slot, starting at midnight.
+pub fn status_grid(segments: &[Segment]) -> [Option<DutyStatus>; 96] {
+    std::array::from_fn(|slot| {
+        let slot_start = slot as f32 * 0.25;
+        dominant_status(segments, slot_start, slot_start + 0.25)
+    })
+}
+
+/// Samples the day's duty status in buckets of the given length.
+///
+/// Like `status_grid`, each bucket takes the status that covers most of it, with ties
+/// going to the status listed first in `DutyStatus::ALL`, and `None` when no segment
+/// touches it. The last bucket is shorter when `bucket_minutes` does not divide the day.
+///
+/// # Parameters
+/// - `segments`: A slice of `Segment` structs representing the driver's log.
+/// - `bucket_minutes`: The length of each bucket, in minutes.
+///
+/// # Returns
+/// - `Vec<Option<DutyStatus>>`: The status of each bucket, starting at midnight; empty
+///   when `bucket_minutes` is `0`.
+pub fn bucket_statuses(segments: &[Segment], bucket_minutes: u32) -> Vec<Option<DutyStatus>> {
+    if bucket_minutes == 0 {
+        return Vec::new();
+    }
+    (0..(24 * 60_u32).div_ceil(bucket_minutes))
+        .map(|bucket| {
+            let start = (bucket * bucket_minutes) as f32 / 60.0;
+            let end = (start + bucket_minutes as f32 / 60.0).min(24.0);
+            dominant_status(segments, start, end)
+        })
+        .collect()
+}
+
+/// Returns the status covering most of `start..end`, or `None` if no segment touches it.
+fn dominant_status(segments: &[Segment], start: f32, end: f32) -> Option<DutyStatus> {
+    let mut coverage = [0.0_f32; 6];
+    for segment in segments {
+        let overlap = segment.end_hour.min(end) - segment.start_hour.max(start);
+        if overlap > 0.0 {
+            coverage[segment.status.index()] += overlap;
+        }
+    }
+    DutyStatus::ALL
+        .into_iter()
+        .zip(coverage)
+        .filter(|(_, hours)| *hours > 0.0)
+        .fold(
+            None,
+            |best: Option<(DutyStatus, f32)>, (status, hours)| match best {
+                Some((_, best_hours)) if best_hours >= hours => best,
+                _ => Some((status, hours)),
+            },
+        )
+        .map(|(status, _)| status)
+}
+
+/// Hours logged per duty status, with a field for each `DutyStatus` variant.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct DutyTotals {
+    pub off_duty: f32,
+    pub sleeper: f32,
+    pub driving: f32,
+    pub on_duty: f32,
+    pub personal_conveyance: f32,
+    pub yard_move: f32,
+}
+
+/// Sums the hours logged in each duty status into named fields.
+///
+/// Overlapping segments are not merged, so overlapping time is counted once per segment.
+///
+/// # Parameters
+/// - `segments`: A slice of `Segment` structs representing the driver's log.
+///
+/// # Returns
+/// - `DutyTotals`: The hours per status; all zero for an empty log.
+pub fn duty_totals(segments: &[Segment]) -> DutyTotals {
+    let [off_duty, sleeper, driving, on_duty, personal_conveyance, yard_move] =
+        status_totals(segments);
+    DutyTotals {
+        off_duty,
+        sleeper,
+        driving,
+        on_duty,
+        personal_conveyance,
+        yard_move,
+    }
+}
+
+/// Computes each duty status's share of the 24-hour day.
+///
+/// The shares sum to at most `1.0`; any remainder is time not covered by the log.
+///
+/// # Parameters
+/// - `segments`: A slice of `Segment` structs representing the driver's log.
+///
+/// # Returns
+/// - `[f32; 6]`: The fraction of the day per status, in the order of `DutyStatus::ALL`.
+pub fn status_percentages(segments: &[Segment]) -> [f32; 6] {
+    status_totals(segments).map(|hours| hours / 24.0)
+}
+
+/// A normalized duty period for export to scheduling and calendar tools.
+///
+/// # Fields
+/// - `start`: The starting hour of the event (in 24-hour format).
+/// - `end`: The ending hour of the event (in 24-hour format).
+/// - `status`: The duty status during the event.
+/// - `label`: A display label combining the status and location.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DutyEvent {
+    pub start: f32,
+    pub end: f32,
+    pub status: DutyStatus,
+    pub label: String,
+}
+
+/// Converts the log into an ordered, Gantt-style list of events.
+///
+/// Contiguous segments sharing a status are coalesced first (see `merge_adjacent`), so
+/// a new event starts at every status change. Each label is the status followed by the
+/// event's first location, e.g. `"Driving - Dallas, TX"`, or just the status when the
+/// location is empty.
+///
+/// # Parameters
+/// - `segments`: A slice of `Segment` structs representing the driver's log.
+///
+/// # Returns
+/// - `Vec<DutyEvent>`: One event per status period, sorted by `start`.
+pub fn to_events(segments: &[Segment]) -> Vec<DutyEvent> {
+    merge_adjacent(segments)
+        .into_iter()
+        .map(|segment| DutyEvent {
+            start: segment.start_hour,
+            end: segment.end_hour,
+            label: if segment.location.is_empty() {
+                segment.status.to_string()
+            } else {
+                format!("{} - {}", segment.status, segment.location)
+            },
+            status: segment.status,
+        })
+        .collect()
+}
+
+/// Renders the log as four rows of Unicode block characters.
+///
+/// Each row is one charted status (Off Duty, Sleeper, Driving, On Duty), prefixed with
+/// a short label. The `width_cols` columns split the 24-hour day evenly; a column shows
+/// `█` when a segment of that status overlaps it and `·` otherwise. Personal conveyance
+/// is shown on the Off Duty row and yard moves on the On Duty row.
+///
+/// # Parameters
+/// - `segments`: A slice of `Segment` structs representing the driver's log.
+/// - `width_cols`: The number of character columns used for the 24 hours.
+///
+/// # Returns
+/// - `String`: The four rows, separated by newlines.
+pub fn render_ascii(segments: &[Segment], width_cols: usize) -> String {
+    let hours_per_col = 24.0 / width_cols as f32;
+
+    DutyStatus::ALL[..4]
+        .iter()
+        .enumerate()
+        .map(|(row, status)| {
+            let cells: String = (0..width_cols)
+                .map(|col| {
+                    let (start, end) =
+                        (col as f32 * hours_per_col, (col + 1) as f32 * hours_per_col);
+                    let active = segments.iter().any(|segment| {
+                        status_row(&segment.status) == row
+                            && segment.start_hour < end
+                            && segment.end_hour > start
+                    });
+                    if active {
+                        '█'
+                    } else {
+                        '·'
+                    }
+                })
+                .collect();
+            format!("{:<4}|{}|", status.abbreviation(), cells)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Groups consecutive segments that share a location into runs.
+///
+/// Segments are taken in `start_hour` order. A new run begins whenever the location
+/// changes, so each run marks one stop, e.g. three back-to-back segments in
+/// `"Dallas, TX"` yield a single run spanning all three.
+///
+/// # Parameters
+/// - `segments`: A slice of `Segment` structs representing the driver's log.
+///
+/// # Returns
+/// - `Vec<(f32, f32, String)>`: The `(start, end, location)` of each run.
+pub fn location_runs(segments: &[Segment]) -> Vec<(f32, f32, String)> {
+    let mut sorted: Vec<&Segment> = segments.iter().collect();
+    sorted.sort_by(|a, b| a.start_hour.total_cmp(&b.start_hour));
+
+    let mut runs: Vec<(f32, f32, String)> = Vec::new();
+    for segment in sorted {
+        match runs.last_mut() {
+            Some((_, end, location)) if *location == segment.location => {
+                *end = end.max(segment.end_hour);
+            }
+            _ => runs.push((
+                segment.start_hour,
+                segment.end_hour,
+                segment.location.clone(),
+            )),
+        }
+    }
+    runs
+}
+
+/// Finds the parts of the 24-hour day that no segment covers.
+///
+/// Overlapping segments are merged before looking for gaps, and hours outside the
+/// 0-24 range are ignored.
+///
+/// # Parameters
+/// - `segments`: A slice of `Segment` structs representing the driver's log.
+///
+/// # Returns
+/// - `Vec<(f32, f32)>`: The `(start, end)` of each gap in chronological order; empty
+///   when the whole day is accounted for.
+pub fn find_gaps(segments: &[Segment]) -> Vec<(f32, f32)> {
+    let mut sorted: Vec<&Segment> = segments.iter().collect();
+    sorted.sort_by(|a, b| a.start_hour.total_cmp(&b.start_hour));
+
+    let mut gaps = Vec::new();
+    let mut reached = 0.0_f32;
+    for segment in sorted {
+        let start = segment.start_hour.clamp(0.0, 24.0);
+        if start > reached {
+            gaps.push((reached, start));
+        }
+        reached = reached.max(segment.end_hour.clamp(0.0, 24.0));
+    }
+    if reached < 24.0 {
+        gaps.push((reached, 24.0));
+    }
+    gaps
+}
+
+/// Fills every gap found by `find_gaps` with a segment of the given status.
+///
+/// The new segments have an empty location and note, and the log is left sorted by
+/// `start_hour`. A fully covered day is left unchanged.
+///
+/// # Parameters
+/// - `segments`: The driver's log, updated in place.
+/// - `status`: The duty status recorded for the unaccounted time.
+pub fn fill_gaps(segments: &mut Vec<Segment>, status: DutyStatus) {
+    let gaps = find_gaps(segments);
+    if gaps.is_empty() {
+        return;
+    }
+    segments.extend(
+        gaps.into_iter()
+            .map(|(start, end)| Segment::builder(start, end, status.clone()).build()),
+    );
+    segments.sort_by(|a, b| a.start_hour.total_cmp(&b.start_hour));
+}
+
+/// Computes the share of the 24-hour day that no segment covers.
+///
+/// A high value flags an incomplete log.
+///
+/// # Parameters
+/// - `segments`: A slice of `Segment` structs representing the driver's log.
+///
+/// # Returns
+/// - `f32`: The total gap hours from `find_gaps` divided by 24, from `0.0` for a fully
+///   covered day to `1.0` for an empty log.
+pub fn gap_percentage(segments: &[Segment]) -> f32 {
+    find_gaps(segments)
+        .iter()
+        .map(|(start, end)| end - start)
+        .sum::<f32>()
+        / 24.0
+}
+
+/// Finds the earliest gap in the log long enough to hold a new segment.
+///
+/// Useful to suggest where a new period could be added in an editor.
+///
+/// # Parameters
+/// - `segments`: A slice of `Segment` structs representing the driver's log.
+/// - `min_hours`: The minimum length of the gap, in hours.
+///
+/// # Returns
+/// - `Some((f32, f32))`: The `(start, end)` of the first gap lasting at least `min_hours`.
+/// - `None`: If no gap is long enough.
+pub fn first_gap_of_at_least(segments: &[Segment], min_hours: f32) -> Option<(f32, f32)> {
+    find_gaps(segments)
+        .into_iter()
+        .find(|(start, end)| end - start >= min_hours)
+}
+
+/// Classifies a gap in the log given the adjacent days.
+///
+/// - `Interior`: A gap that the adjacent days cannot explain and should be fixed.
+/// - `LeadingContinued`: A gap at the start of the day, continuing the prior day's status.
+/// - `TrailingContinued`: A gap at the end of the day, continued by the next day's log.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GapKind {
+    Interior,
+    LeadingContinued,
+    TrailingContinued,
+}
+
+/// Finds the gaps in the log, taking the adjacent days into account.
+///
+/// A gap starting at midnight is `GapKind::LeadingContinued` when the prior day ended in
+/// the same status as the first segment after the gap, since the driver simply remained
+/// in that status. Likewise, a gap running to the end of the day is
+/// `GapKind::TrailingContinued` when the next day starts in the same status as the last
+/// segment before the gap. An empty log's whole-day gap is `GapKind::LeadingContinued`
+/// when the prior and next days agree. Every other gap is `GapKind::Interior`.
+///
+/// # Parameters
+/// - `segments`: A slice of `Segment` structs representing the driver's log.
+/// - `prior_status`: The last status of the previous day, if that log is available.
+/// - `next_status`: The first status of the next day, if that log is available.
+///
+/// # Returns
+/// - `Vec<(f32, f32, GapKind)>`: The `(start, end, kind)` of each gap in chronological order.
+pub fn find_gaps_with_context(
+    segments: &[Segment],
+    prior_status: Option<&DutyStatus>,
+    next_status: Option<&DutyStatus>,
+) -> Vec<(f32, f32, GapKind)> {
+    let status_ending_at = |hour: f32| {
+        segments
+            .iter()
+            .find(|segment| segment.end_hour == hour)
+            .map(|segment| segment.status.clone())
+    };
+    find_gaps(segments)
+        .into_iter()
+        .map(|(start, end)| {
+            let (before, after) = if segments.is_empty() {
+                (prior_status.cloned(), next_status.cloned())
+            } else {
+                (status_ending_at(start), status_at(segments, end))
+            };
+            let kind = if start == 0.0 && prior_status.is_some_and(|s| after.as_ref() == Some(s)) {
+                GapKind::LeadingContinued
+            } else if end == 24.0 && next_status.is_some_and(|s| before.as_ref() == Some(s)) {
+                GapKind::TrailingContinued
+            } else {
+                GapKind::Interior
+            };
+            (start, end, kind)
+        })
+        .collect()
+}
+
+/// A problem with how a log covers its day, as found by `check_dst_day`.
+///
+/// - `Gap`: Time between `start` and `end` that no segment covers.
+/// - `Overlap`: Time between `start` and `end` covered by both segment `first` and
+///   segment `second` (indices into the log).
+/// - `OutOfDay`: Segment `index` starts before midnight or ends after the day does.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LogIssue {
+    Gap {
+        start: f32,
+        end: f32,
+    },
+    Overlap {
+        start: f32,
+        end: f32,
+        first: usize,
+        second: usize,
+    },
+    OutOfDay {
+        index: usize,
+    },
+}
+
+/// Checks that a log covers a day of the given length exactly once.
+///
+/// On the day clocks spring forward the local day lasts 23 hours, and on the day they
+/// fall back it lasts 25, so a log for those days must end at hour 23 or 25 instead of
+/// 24. Hours count elapsed time from midnight, so on a fall-back day the repeated
+/// 1 AM hour spans hours 1 to 3.
+///
+/// # Parameters
+/// - `segments`: A slice of `Segment` structs representing the driver's log.
+/// - `day_length_hours`: The length of the local day, e.g. `23.0`, `24.0`, or `25.0`.
+///
+/// # Returns
+/// - `Vec<LogIssue>`: The gaps, overlaps, and out-of-day segments, in the order they
+///   occur in the day; empty when the log covers the whole day exactly once.
+pub fn check_dst_day(segments: &[Segment], day_length_hours: f32) -> Vec<LogIssue> {
+    let mut sorted: Vec<(usize, &Segment)> = segments.iter().enumerate().collect();
+    sorted.sort_by(|a, b| a.1.start_hour.total_cmp(&b.1.start_hour));
+
+    let mut issues = Vec::new();
+    let mut reached = 0.0_f32;
+    let mut furthest: Option<(usize, f32)> = None;
+    for (index, segment) in sorted {
+        if segment.start_hour < 0.0 || segment.end_hour > day_length_hours {
+            issues.push(LogIssue::OutOfDay { index });
+        }
+        let start = segment.start_hour.clamp(0.0, day_length_hours);
+        if start > reached {
+            issues.push(LogIssue::Gap {
+                start: reached,
+                end: start,
+            });
+        }
+        if let Some((first, end)) = furthest.filter(|&(_, end)| segment.start_hour < end) {
+            issues.push(LogIssue::Overlap {
+                start: segment.start_hour,
+                end: segment.end_hour.min(end),
+                first,
+                second: index,
+            });
+        }
+        reached = reached.max(segment.end_hour.clamp(0.0, day_length_hours));
+        if furthest.is_none_or(|(_, end)| segment.end_hour > end) {
+            furthest = Some((index, segment.end_hour));
+        }
+    }
+    if reached < day_length_hours {
+        issues.push(LogIssue::Gap {
+            start: reached,
+            end: day_length_hours,
+        });
+    }
+    issues
+}
+
+/// Returns a representative, compliant day of driving for demos and tests.
+///
+/// The driver is off duty overnight, does a pre-trip inspection, drives with a
+/// 30-minute break mid-day, fuels, and spends the rest of the day in the sleeper berth.
+/// Every segment has a location and a note, and together they cover the full 24 hours.
+///
+/// # Returns
+/// - `Vec<Segment>`: The segments of the sample day, in chronological order.
+pub fn sample_day() -> Vec<Segment> {
+    vec![
+        Segment::builder(0.0, 6.0, DutyStatus::OffDuty)
+            .location("Dallas, TX")
+            .note("Home terminal")
+            .build(),
+        Segment::builder(6.0, 6.5, DutyStatus::OnDuty)
+            .location("Dallas, TX")
+            .note("Pre-trip inspection")
+            .build(),
+        Segment::builder(6.5, 11.0, DutyStatus::Driving)
+            .location("Dallas, TX")
+            .note("Departed with load")
+            .build(),
+        Segment::builder(11.0, 11.5, DutyStatus::OffDuty)
+            .location("Texarkana, TX")
+            .note("30-minute break")
+            .build(),
+        Segment::builder(11.5, 15.5, DutyStatus::Driving)
+            .location("Texarkana, TX")
+            .note("Resumed trip")
+            .build(),
+        Segment::builder(15.5, 16.0, DutyStatus::OnDuty)
+            .location("Memphis, TN")
+            .note("Fueling")
+            .build(),
+        Segment::builder(16.0, 24.0, DutyStatus::Sleeper)
+            .location("Memphis, TN")
+            .note("Sleeper berth")
+            .build(),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(start_hour: f32, end_hour: f32, status: DutyStatus) -> Segment {
+        Segment::builder(start_hour, end_hour, status).build()
+    }
+
+    fn lines(commands: &[DrawCommand]) -> Vec<(f64, f64, f64, f64, &str, bool)> {
+        commands
+            .iter()
+            .filter_map(|command| match command {
+                DrawCommand::Line {
+                    x1,
+                    y1,
+                    x2,
+                    y2,
+                    color,
+                    dashed,
+                } => Some((*x1, *y1, *x2, *y2, color.as_str(), *dashed)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn segment_rects_joins_status_changes_with_vertical_steps() {
+        let log = [
+            segment(0.0, 6.0, DutyStatus::OffDuty),
+            segment(6.0, 10.0, DutyStatus::Driving),
+            segment(10.0, 24.0, DutyStatus::OnDuty),
+        ];
+        // A 720-pixel grid gives every hour 30 pixels.
+        let props = ChartProps {
+            width: 860,
+            ..ChartProps::default()
+        };
+        let commands = segment_rects(&log, &props);
+        let lines = lines(&commands);
+
+        assert!(lines.contains(&(250.0, 67.5, 250.0, 177.5, "green", false)));
+        assert!(lines.contains(&(370.0, 177.5, 370.0, 232.5, "orange", false)));
+        assert!(lines.contains(&(70.0, 67.5, 250.0, 67.5, "#8E8E8E", false)));
+        assert!(lines.contains(&(370.0, 232.5, 790.0, 232.5, "orange", false)));
+    }
+
+    #[test]
+    fn segment_rects_clips_to_start_and_end_hour() {
+        let log = [
+            segment(0.0, 8.0, DutyStatus::OffDuty),
+            segment(8.0, 20.0, DutyStatus::Driving),
+            segment(20.0, 24.0, DutyStatus::OffDuty),
+        ];
+        // Twelve hours over a 720-pixel grid gives every hour 60 pixels.
+        let props = ChartProps {
+            width: 860,
+            start_hour: 6.0,
+            end_hour: 18.0,
+            ..ChartProps::default()
+        };
+        let commands = segment_rects(&log, &props);
+        let lines = lines(&commands);
+
+        assert_eq!(
+            lines,
+            vec![
+                (190.0, 67.5, 190.0, 177.5, "green", false),
+                (70.0, 67.5, 190.0, 67.5, "#8E8E8E", false),
+                (190.0, 177.5, 790.0, 177.5, "green", false),
+            ]
+        );
+    }
+
+    #[test]
+    fn segment_rects_dashes_personal_conveyance_and_yard_move() {
+        let log = [
+            segment(0.0, 2.0, DutyStatus::PersonalConveyance),
+            segment(2.0, 4.0, DutyStatus::YardMove),
+            segment(4.0, 24.0, DutyStatus::OffDuty),
+        ];
+        let commands = segment_rects(&log, &ChartProps::default());
+        let lines = lines(&commands);
+
+        let dashed: Vec<_> = lines.iter().filter(|line| line.5).collect();
+        assert_eq!(dashed.len(), 2);
+        assert_eq!(dashed[0].4, "steelblue");
+        assert_eq!(dashed[1].4, "goldenrod");
+        assert_eq!(dashed[0].1, dashed[0].3);
+        assert!(lines.iter().all(|line| line.5 || line.4 != "steelblue"));
+    }
+
+    #[test]
+    fn segment_rects_fills_the_grid_height_in_ribbon_style() {
+        let log = [
+            segment(0.0, 6.0, DutyStatus::OffDuty),
+            segment(6.0, 24.0, DutyStatus::Driving),
+        ];
+        let props = ChartProps {
+            style: ChartStyle::Ribbon,
+            ..ChartProps::default()
+        };
+
+        assert_eq!(
+            segment_rects(&log, &props),
+            vec![
+                DrawCommand::Rect {
+                    x: 70.0,
+                    y: 40.0,
+                    width: 190.0,
+                    height: 220.0,
+                    color: "#8E8E8E".to_string(),
+                    dashed: false,
+                },
+                DrawCommand::Rect {
+                    x: 260.0,
+                    y: 40.0,
+                    width: 570.0,
+                    height: 220.0,
+                    color: "green".to_string(),
+                    dashed: false,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn segment_rects_inverts_the_rows_with_flip_y() {
+        let log = [
+            segment(0.0, 6.0, DutyStatus::OffDuty),
+            segment(6.0, 24.0, DutyStatus::Driving),
+        ];
+        let props = ChartProps {
+            width: 860,
+            flip_y: true,
+            ..ChartProps::default()
+        };
+        let commands = segment_rects(&log, &props);
+        let lines = lines(&commands);
+
+        // Driving moves from the third row to the second, Off Duty to the bottom row.
+        assert!(lines.contains(&(250.0, 122.5, 790.0, 122.5, "green", false)));
+        assert!(lines.contains(&(70.0, 232.5, 250.0, 232.5, "#8E8E8E", false)));
+        assert_eq!(row_label(display_row(0, &props), &props), "On Duty");
+    }
+
+    #[test]
+    fn transitions_only_grid_draws_one_vertical_per_transition() {
+        let log = [
+            segment(0.0, 6.0, DutyStatus::OffDuty),
+            segment(6.0, 10.5, DutyStatus::Driving),
+            segment(10.5, 11.0, DutyStatus::OffDuty),
+            segment(11.0, 24.0, DutyStatus::OnDuty),
+        ];
+        let props = |grid_mode| ChartProps {
+            grid_mode,
+            ..ChartProps::default()
+        };
+
+        assert_eq!(
+            vertical_grid_hours(&log, &props(GridMode::TransitionsOnly)),
+            vec![0.0, 6.0, 10.5, 11.0, 24.0]
+        );
+        assert_eq!(
+            vertical_grid_hours(&log, &props(GridMode::Hourly)).len(),
+            25
+        );
+        assert!(vertical_grid_hours(&log, &props(GridMode::None)).is_empty());
+    }
+
+    #[test]
+    fn integer_layout_puts_the_grid_on_whole_pixels() {
+        let log = [
+            segment(0.0, 6.1, DutyStatus::OffDuty),
+            segment(6.1, 24.0, DutyStatus::Driving),
+        ];
+        let props = ChartProps {
+            width: 907,
+            height: 301,
+            integer_layout: true,
+            ..ChartProps::default()
+        };
+        let (width, height) = (907.0, 301.0);
+
+        for hour in 0..=24 {
+            let x = hour_to_x(hour as f32, width, &props);
+            assert_eq!(x, x.round(), "hour {hour}");
+        }
+        assert_eq!(hour_to_x(24.0, width, &props), width - PADDING_X);
+        for row in 0..=4 {
+            let y = row_y(row, height, &props);
+            assert_eq!(y, y.round(), "row {row}");
+        }
+        for (x1, y1, x2, y2, _, _) in lines(&segment_rects(&log, &props)) {
+            assert!([x1, y1, x2, y2].iter().all(|value| *value == value.round()));
+        }
+    }
+
+    #[test]
+    fn row_heights_weight_the_rows() {
+        let props = ChartProps {
+            width: 860,
+            row_heights: Some([1.0, 1.0, 2.0, 1.0]),
+            ..ChartProps::default()
+        };
+        // 220 pixels of rows split in fifths of 44 pixels.
+        let rows: Vec<f64> = (0..=4).map(|row| row_y(row, 300.0, &props)).collect();
+        assert_eq!(rows, vec![40.0, 84.0, 128.0, 216.0, 260.0]);
+        assert_eq!(status_y(&DutyStatus::Driving, 300.0, &props), 172.0);
+
+        let log = [segment(0.0, 24.0, DutyStatus::Driving)];
+        let commands = segment_rects(&log, &props);
+        assert_eq!(
+            lines(&commands),
+            vec![(70.0, 172.0, 790.0, 172.0, "green", false)]
+        );
+    }
+
+    #[test]
+    fn team_drivers_share_a_row_at_distinct_heights() {
+        let props = ChartProps::default();
+        let (top, bottom) = (row_y(2, 300.0, &props), row_y(3, 300.0, &props));
+        let driver_a = team_y(&DutyStatus::Driving, false, 300.0, &props);
+        let driver_b = team_y(&DutyStatus::Driving, true, 300.0, &props);
+
+        assert!(top < driver_a && driver_a < driver_b && driver_b < bottom);
+        assert!((driver_a - (top + 55.0 / 3.0)).abs() < 1e-9);
+        assert!((driver_b - (top + 110.0 / 3.0)).abs() < 1e-9);
+        assert_eq!(
+            team_y(&DutyStatus::YardMove, true, 300.0, &props),
+            team_y(&DutyStatus::OnDuty, true, 300.0, &props)
+        );
+    }
+
+    #[test]
+    fn segment_rects_draws_one_pill_per_segment_in_pill_style() {
+        let log = [
+            segment(0.0, 6.0, DutyStatus::OffDuty),
+            segment(6.0, 24.0, DutyStatus::Driving),
+        ];
+        let props = ChartProps {
+            width: 860,
+            segment_style: SegmentStyle::Pill,
+            ..ChartProps::default()
+        };
+
+        assert_eq!(
+            segment_rects(&log, &props),
+            vec![
+                DrawCommand::Pill {
+                    x: 70.0,
+                    y: 61.5,
+                    width: 180.0,
+                    height: 12.0,
+                    color: "#8E8E8E".to_string(),
+                    dashed: false,
+                },
+                DrawCommand::Pill {
+                    x: 250.0,
+                    y: 171.5,
+                    width: 540.0,
+                    height: 12.0,
+                    color: "green".to_string(),
+                    dashed: false,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn segment_rects_strokes_driving_past_eleven_hours_in_the_overage_color() {
+        let log = [
+            segment(0.0, 6.0, DutyStatus::OffDuty),
+            segment(6.0, 18.0, DutyStatus::Driving),
+            segment(18.0, 24.0, DutyStatus::OffDuty),
+        ];
+        let props = ChartProps {
+            width: 860,
+            highlight_overage: true,
+            ..ChartProps::default()
+        };
+        let overage = |props: &ChartProps| -> Vec<(f64, f64, f64, f64)> {
+            lines(&segment_rects(&log, props))
+                .into_iter()
+                .filter(|line| line.4 == "red")
+                .map(|(x1, y1, x2, y2, _, _)| (x1, y1, x2, y2))
+                .collect()
+        };
+
+        // The limit is reached at 17:00, so only the last hour is highlighted.
+        assert_eq!(overage(&props), vec![(580.0, 177.5, 610.0, 177.5)]);
+        let props = ChartProps {
+            highlight_overage: false,
+            ..props
+        };
+        assert!(overage(&props).is_empty());
+    }
+
+    #[test]
+    fn segment_rects_widens_short_segments_to_min_segment_px() {
+        let five_minutes = 5.0 / 60.0;
+        let log = [
+            segment(0.0, 12.0, DutyStatus::OffDuty),
+            segment(12.0, 12.0 + five_minutes, DutyStatus::OnDuty),
+            segment(12.0 + five_minutes, 24.0, DutyStatus::OffDuty),
+        ];
+        let props = ChartProps {
+            width: 2000,
+            min_segment_px: 20.0,
+            ..ChartProps::default()
+        };
+        let commands = segment_rects(&log, &props);
+        let lines = lines(&commands);
+
+        let &(x1, _, x2, _, _, _) = lines
+            .iter()
+            .find(|line| line.4 == "orange" && line.1 == line.3)
+            .unwrap();
+        assert!(x2 - x1 >= 20.0 - 1e-9);
+        // The widened segment stays centered on its true position.
+        let (true_start, true_end) = (
+            hour_to_x(12.0, 2000.0, &props),
+            hour_to_x(12.0 + five_minutes, 2000.0, &props),
+        );
+        assert!(true_end - true_start < 20.0);
+        assert!(((x1 + x2) / 2.0 - (true_start + true_end) / 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn align_offset_places_a_narrower_chart_in_the_canvas() {
+        let props = |align| ChartProps {
+            max_width: Some(600),
+            align,
+            ..ChartProps::default()
+        };
+        assert_eq!(align_offset(1000.0, &props(Align::Left)), 0.0);
+        assert_eq!(align_offset(1000.0, &props(Align::Center)), 200.0);
+        assert_eq!(align_offset(1000.0, &props(Align::Right)), 400.0);
+        // A chart that fills the canvas is never shifted.
+        assert_eq!(align_offset(500.0, &props(Align::Center)), 0.0);
+
+        let snapped = ChartProps {
+            integer_layout: true,
+            ..props(Align::Center)
+        };
+        assert_eq!(align_offset(1001.0, &snapped), 201.0);
+    }
+
+    #[test]
+    fn segment_at_follows_the_centered_grid() {
+        let log = [segment(0.0, 24.0, DutyStatus::Driving)];
+        let props = ChartProps {
+            width: 1000,
+            max_width: Some(600),
+            align: Align::Center,
+            ..ChartProps::default()
+        };
+        // The grid starts at x = 70 + 200.
+        assert_eq!(segment_at(&log, &props, 260.0, 177.5), None);
+        assert_eq!(segment_at(&log, &props, 280.0, 177.5), Some(0));
+    }
+
+    #[test]
+    fn segment_rects_culls_segments_outside_the_visible_range() {
+        let log = [
+            segment(0.0, 4.0, DutyStatus::OffDuty),
+            segment(4.0, 10.0, DutyStatus::Driving),
+            segment(10.0, 24.0, DutyStatus::OnDuty),
+        ];
+        let props = ChartProps {
+            width: 860,
+            visible_range: Some((200.0, 360.0)),
+            ..ChartProps::default()
+        };
+        let commands = segment_rects(&log, &props);
+
+        // Only the driving segment, from x = 190 to 370, reaches into the range; the
+        // steps at either end of it are outside.
+        assert_eq!(
+            lines(&commands),
+            vec![(190.0, 177.5, 370.0, 177.5, "green", false)]
+        );
+    }
+
+    #[test]
+    fn row_label_abbreviates_with_compact_labels() {
+        let full = ChartProps::default();
+        let compact = ChartProps {
+            compact_labels: true,
+            ..ChartProps::default()
+        };
+        let labels = |props: &ChartProps| -> Vec<&str> {
+            (0..4)
+                .map(|row| row_label(display_row(row, props), props))
+                .collect()
+        };
+        assert_eq!(
+            labels(&full),
+            vec!["Off Duty", "Sleeper", "Driving", "On Duty"]
+        );
+        assert_eq!(labels(&compact), vec!["OFF", "SB", "D", "ON"]);
+    }
+
+    #[test]
+    fn odometer_readings_are_placed_at_each_segment_start() {
+        let log = [
+            Segment::builder(0.0, 6.0, DutyStatus::OffDuty)
+                .odometer(1200.0)
+                .build(),
+            Segment::builder(6.0, 10.0, DutyStatus::Driving)
+                .odometer(1234.4)
+                .build(),
+            segment(10.0, 12.0, DutyStatus::OnDuty),
+            Segment::builder(12.0, 24.0, DutyStatus::Driving)
+                .odometer(1498.6)
+                .build(),
+        ];
+        let props = ChartProps {
+            width: 860,
+            show_odometer: true,
+            ..ChartProps::default()
+        };
+        let readings = odometer_readings(&log, &props);
+        assert_eq!(
+            readings,
+            vec![
+                (0.0, "1200".to_string()),
+                (6.0, "1234".to_string()),
+                (12.0, "1499".to_string()),
+            ]
+        );
+        assert_eq!(hour_to_x(readings[1].0, 860.0, &props), 250.0);
+
+        let zoomed = ChartProps {
+            start_hour: 8.0,
+            ..props
+        };
+        assert_eq!(
+            odometer_readings(&log, &zoomed),
+            vec![(12.0, "1499".to_string())]
+        );
+    }
+
+    #[test]
+    fn container_class_renders_a_given_class_and_omits_a_blank_one() {
+        assert_eq!(container_class("log-chart"), Some("log-chart"));
+        assert_eq!(container_class("  log-chart wide "), Some("log-chart wide"));
+        assert_eq!(container_class(""), None);
+        assert_eq!(container_class("   "), None);
+    }
+
+    #[test]
+    fn redraw_base_redraws_only_against_a_chart_drawn_with_the_same_props() {
+        let props = ChartProps::default();
+        let previous = vec![segment(0.0, 24.0, DutyStatus::OffDuty)];
+        let wider = ChartProps {
+            width: props.width + 100,
+            ..props.clone()
+        };
+
+        assert_eq!(
+            redraw_base(Some(&previous), Some(&props), &props),
+            Some(previous.as_slice())
+        );
+        assert_eq!(redraw_base(Some(&previous), Some(&wider), &props), None);
+        assert_eq!(redraw_base(Some(&previous), None, &props), None);
+        assert_eq!(redraw_base(None, Some(&props), &props), None);
+    }
+
+    #[test]
+    fn forgetting_a_grid_marks_only_that_canvas_as_not_drawn() {
+        mark_grid_as_drawn("first-chart", 1);
+        mark_grid_as_drawn("second-chart", 2);
+        assert!(grid_already_drawn("first-chart", 1));
+        assert!(!grid_already_drawn("first-chart", 2));
+
+        // `clear_chart` forgets the grid this way once the canvas is cleared.
+        forget_grid("first-chart");
+        assert!(!grid_already_drawn("first-chart", 1));
+        assert!(grid_already_drawn("second-chart", 2));
+
+        reset_grid_cache();
+        assert!(!grid_already_drawn("second-chart", 2));
+    }
+
+    #[test]
+    fn find_gaps_with_context_tells_interior_gaps_from_boundary_gaps() {
+        let log = [
+            segment(2.0, 8.0, DutyStatus::OffDuty),
+            segment(9.0, 20.0, DutyStatus::Driving),
+        ];
+        let off_duty = DutyStatus::OffDuty;
+        let driving = DutyStatus::Driving;
+
+        assert_eq!(
+            find_gaps_with_context(&log, Some(&off_duty), Some(&driving)),
+            vec![
+                (0.0, 2.0, GapKind::LeadingContinued),
+                (8.0, 9.0, GapKind::Interior),
+                (20.0, 24.0, GapKind::TrailingContinued),
+            ]
+        );
+        assert_eq!(
+            find_gaps_with_context(&log, None, None),
+            vec![
+                (0.0, 2.0, GapKind::Interior),
+                (8.0, 9.0, GapKind::Interior),
+                (20.0, 24.0, GapKind::Interior),
+            ]
+        );
+    }
+
+    #[test]
+    fn find_gaps_with_context_requires_the_adjacent_status_to_match() {
+        let log = [
+            segment(2.0, 8.0, DutyStatus::OffDuty),
+            segment(8.0, 20.0, DutyStatus::Driving),
+        ];
+        let sleeper = DutyStatus::Sleeper;
+
+        assert_eq!(
+            find_gaps_with_context(&log, Some(&sleeper), Some(&sleeper)),
+            vec![
+                (0.0, 2.0, GapKind::Interior),
+                (20.0, 24.0, GapKind::Interior)
+            ]
+        );
+    }
+
+    #[test]
+    fn find_gaps_with_context_continues_an_empty_day_only_between_matching_days() {
+        let off_duty = DutyStatus::OffDuty;
+        let driving = DutyStatus::Driving;
+
+        assert_eq!(
+            find_gaps_with_context(&[], Some(&off_duty), Some(&off_duty)),
+            vec![(0.0, 24.0, GapKind::LeadingContinued)]
+        );
+        assert_eq!(
+            find_gaps_with_context(&[], Some(&off_duty), Some(&driving)),
+            vec![(0.0, 24.0, GapKind::Interior)]
+        );
+    }
+
+    #[test]
+    fn effective_width_clamps_an_oversized_container_to_max_width() {
+        let props = ChartProps {
+            min_width: Some(400),
+            max_width: Some(1200),
+            ..ChartProps::default()
+        };
+        assert_eq!(effective_width(3840.0, &props), 1200);
+        assert_eq!(effective_width(1000.0, &props), 1000);
+    }
+
+    #[test]
+    fn effective_width_floors_a_narrow_container_at_min_width() {
+        let props = ChartProps {
+            min_width: Some(400),
+            ..ChartProps::default()
+        };
+        assert_eq!(effective_width(320.0, &props), 400);
+        assert_eq!(effective_width(2000.0, &props), 2000);
+    }
+
+    #[test]
+    fn effective_width_keeps_the_fixed_width_without_bounds() {
+        assert_eq!(effective_width(3840.0, &ChartProps::default()), 900);
+    }
+
+    #[test]
+    fn check_dst_day_accepts_a_clean_spring_forward_day() {
+        let log = [
+            segment(0.0, 8.0, DutyStatus::OffDuty),
+            segment(8.0, 16.0, DutyStatus::Driving),
+            segment(16.0, 23.0, DutyStatus::Sleeper),
+        ];
+        assert!(check_dst_day(&log, 23.0).is_empty());
+        assert_eq!(
+            check_dst_day(&log, 24.0),
+            vec![LogIssue::Gap {
+                start: 23.0,
+                end: 24.0,
+            }]
+        );
+    }
+
+    #[test]
+    fn check_dst_day_accepts_a_clean_fall_back_day() {
+        let log = [
+            segment(0.0, 8.0, DutyStatus::OffDuty),
+            segment(8.0, 16.0, DutyStatus::Driving),
+            segment(16.0, 25.0, DutyStatus::Sleeper),
+        ];
+        assert!(check_dst_day(&log, 25.0).is_empty());
+    }
+
+    #[test]
+    fn check_dst_day_flags_a_24_hour_log_on_a_23_hour_day() {
+        let log = [
+            segment(0.0, 8.0, DutyStatus::OffDuty),
+            segment(8.0, 24.0, DutyStatus::Driving),
+        ];
+        assert_eq!(
+            check_dst_day(&log, 23.0),
+            vec![LogIssue::OutOfDay { index: 1 }]
+        );
+    }
+
+    #[test]
+    fn check_dst_day_flags_an_overlap_on_the_repeated_hour() {
+        // The second 1 AM hour was logged as if the clock had not fallen back.
+        let log = [
+            segment(0.0, 2.0, DutyStatus::OffDuty),
+            segment(1.0, 3.0, DutyStatus::Sleeper),
+            segment(3.0, 25.0, DutyStatus::OffDuty),
+        ];
+        assert_eq!(
+            check_dst_day(&log, 25.0),
+            vec![LogIssue::Overlap {
+                start: 1.0,
+                end: 2.0,
+                first: 0,
+                second: 1,
+            }]
+        );
+    }
+
+    #[test]
+    fn merge_logs_resolves_an_overlap_per_strategy() {
+        let base = [segment(0.0, 12.0, DutyStatus::OffDuty)];
+        let incoming = [segment(10.0, 14.0, DutyStatus::Driving)];
+
+        assert_eq!(
+            merge_logs(&base, &incoming, MergeStrategy::PreferBase),
+            Ok(vec![
+                segment(0.0, 12.0, DutyStatus::OffDuty),
+                segment(12.0, 14.0, DutyStatus::Driving),
+            ])
+        );
+        assert_eq!(
+            merge_logs(&base, &incoming, MergeStrategy::PreferIncoming),
+            Ok(vec![
+                segment(0.0, 10.0, DutyStatus::OffDuty),
+                segment(10.0, 14.0, DutyStatus::Driving),
+            ])
+        );
+        assert!(merge_logs(&base, &incoming, MergeStrategy::Error).is_err());
+    }
+
+    #[test]
+    fn merge_logs_splits_a_segment_around_a_preferred_one() {
+        let base = [segment(0.0, 12.0, DutyStatus::OffDuty)];
+        let incoming = [segment(4.0, 6.0, DutyStatus::OnDuty)];
+        assert_eq!(
+            merge_logs(&base, &incoming, MergeStrategy::PreferIncoming),
+            Ok(vec![
+                segment(0.0, 4.0, DutyStatus::OffDuty),
+                segment(4.0, 6.0, DutyStatus::OnDuty),
+                segment(6.0, 12.0, DutyStatus::OffDuty),
+            ])
+        );
+    }
+
+    #[test]
+    fn merge_logs_unions_touching_segments_for_every_strategy() {
+        let base = [segment(6.0, 12.0, DutyStatus::Driving)];
+        let incoming = [segment(0.0, 6.0, DutyStatus::OffDuty)];
+        let expected = vec![
+            segment(0.0, 6.0, DutyStatus::OffDuty),
+            segment(6.0, 12.0, DutyStatus::Driving),
+        ];
+        for strategy in [
+            MergeStrategy::PreferBase,
+            MergeStrategy::PreferIncoming,
+            MergeStrategy::Error,
+        ] {
+            assert_eq!(merge_logs(&base, &incoming, strategy), Ok(expected.clone()));
+        }
+    }
+
+    #[test]
+    fn diff_logs_reports_the_changed_hours() {
+        let previous = [
+            segment(0.0, 6.0, DutyStatus::OffDuty),
+            segment(6.0, 12.0, DutyStatus::Driving),
+            segment(12.0, 24.0, DutyStatus::OffDuty),
+        ];
+        let mut segments = previous.to_vec();
+        assert!(diff_logs(&previous, &segments).is_empty());
+
+        segments[1] = segment(6.0, 10.0, DutyStatus::Driving);
+        segments.insert(2, segment(10.0, 12.0, DutyStatus::OnDuty));
+        assert_eq!(diff_logs(&previous, &segments), vec![(6.0, 12.0)]);
+    }
+
+    #[test]
+    fn status_percentages_share_the_day_with_gaps() {
+        let log = [
+            segment(6.0, 12.0, DutyStatus::Driving),
+            segment(12.0, 15.0, DutyStatus::OnDuty),
+        ];
+        let percentages = status_percentages(&log);
+        assert_eq!(percentages[DutyStatus::Driving.index()], 0.25);
+        assert_eq!(percentages[DutyStatus::OnDuty.index()], 0.125);
+        let total = percentages.iter().sum::<f32>() + gap_percentage(&log);
+        assert!((total - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn duty_totals_of_an_empty_log_are_zero() {
+        assert_eq!(duty_totals(&[]), DutyTotals::default());
+    }
+
+    #[test]
+    fn duty_totals_count_overlapping_time_once_per_segment() {
+        let log = [
+            segment(6.0, 10.0, DutyStatus::Driving),
+            segment(8.0, 12.0, DutyStatus::Driving),
+            segment(9.0, 11.0, DutyStatus::OnDuty),
+        ];
+        let totals = duty_totals(&log);
+        assert_eq!(totals.driving, 8.0);
+        assert_eq!(totals.on_duty, 2.0);
+    }
+
+    #[test]
+    fn duty_totals_keep_personal_conveyance_and_yard_moves_apart() {
+        let log = [
+            segment(0.0, 6.0, DutyStatus::OffDuty),
+            segment(6.0, 7.0, DutyStatus::PersonalConveyance),
+            segment(7.0, 7.5, DutyStatus::YardMove),
+            segment(7.5, 8.0, DutyStatus::OnDuty),
+            segment(8.0, 12.0, DutyStatus::Driving),
+            segment(12.0, 24.0, DutyStatus::Sleeper),
+        ];
+        assert_eq!(
+            duty_totals(&log),
+            DutyTotals {
+                off_duty: 6.0,
+                sleeper: 12.0,
+                driving: 4.0,
+                on_duty: 0.5,
+                personal_conveyance: 1.0,
+                yard_move: 0.5,
+            }
+        );
+    }
+
+    #[test]
+    fn render_ascii_marks_the_driving_columns() {
+        let log = [segment(12.0, 18.0, DutyStatus::Driving)];
+        let rendered = render_ascii(&log, 24);
+        let rows: Vec<&str> = rendered.lines().collect();
+        assert_eq!(rows.len(), 4);
+
+        let driving: Vec<char> = rows[2].strip_prefix("D   |").unwrap().chars().collect();
+        assert_eq!(driving.len(), 25);
+        for (col, cell) in driving[..24].iter().enumerate() {
+            let expected = if (12..18).contains(&col) { '█' } else { '·' };
+            assert_eq!(*cell, expected, "column {col}");
+        }
+        assert!(!rows[0].contains('█'));
+    }
+
+    #[test]
+    fn to_events_starts_an_event_at_each_status_change() {
+        let log = [
+            Segment::builder(7.0, 10.0, DutyStatus::Driving)
+                .location("Dallas, TX")
+                .build(),
+            Segment::builder(0.0, 6.0, DutyStatus::OffDuty)
+                .location("Dallas, TX")
+                .build(),
+            Segment::builder(10.0, 13.0, DutyStatus::Driving)
+                .location("Waco, TX")
+                .build(),
+            Segment::builder(6.0, 7.0, DutyStatus::OnDuty)
+                .location("Dallas, TX")
+                .build(),
+            segment(13.0, 24.0, DutyStatus::OffDuty),
+        ];
+        let events: Vec<(f32, f32, String)> = to_events(&log)
+            .into_iter()
+            .map(|event| (event.start, event.end, event.label))
+            .collect();
+        assert_eq!(
+            events,
+            vec![
+                (0.0, 6.0, "OffDuty - Dallas, TX".to_string()),
+                (6.0, 7.0, "OnDuty - Dallas, TX".to_string()),
+                (7.0, 13.0, "Driving - Dallas, TX".to_string()),
+                (13.0, 24.0, "OffDuty".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn location_runs_group_consecutive_stops() {
+        let log = [
+            Segment::builder(0.0, 6.0, DutyStatus::OffDuty)
+                .location("Dallas, TX")
+                .build(),
+            Segment::builder(6.0, 7.0, DutyStatus::OnDuty)
+                .location("Dallas, TX")
+                .build(),
+            Segment::builder(7.0, 8.0, DutyStatus::YardMove)
+                .location("Dallas, TX")
+                .build(),
+            Segment::builder(8.0, 14.0, DutyStatus::Driving)
+                .location("Waco, TX")
+                .build(),
+        ];
+        assert_eq!(
+            location_runs(&log),
+            vec![
+                (0.0, 8.0, "Dallas, TX".to_string()),
+                (8.0, 14.0, "Waco, TX".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn snap_to_neighbors_respects_the_tolerance() {
+        let existing = [segment(0.0, 12.0, DutyStatus::OffDuty)];
+        let new_seg = segment(12.02, 16.0, DutyStatus::Driving);
+
+        let snapped = snap_to_neighbors(&new_seg, &existing, 0.1);
+        assert_eq!(snapped.start_hour, 12.0);
+        assert_eq!(snapped.end_hour, 16.0);
+
+        let unsnapped = snap_to_neighbors(&new_seg, &existing, 0.01);
+        assert_eq!(unsnapped, new_seg);
+    }
+
+    #[test]
+    fn status_grid_samples_a_clean_day() {
+        let log = [
+            segment(0.0, 6.0, DutyStatus::OffDuty),
+            segment(6.0, 12.0, DutyStatus::Driving),
+        ];
+        let grid = status_grid(&log);
+        assert!(grid[..24]
+            .iter()
+            .all(|slot| *slot == Some(DutyStatus::OffDuty)));
+        assert!(grid[24..48]
+            .iter()
+            .all(|slot| *slot == Some(DutyStatus::Driving)));
+        assert!(grid[48..].iter().all(Option::is_none));
+    }
+
+    #[test]
+    fn status_grid_takes_the_dominant_status_of_a_split_slot() {
+        let log = [
+            segment(0.0, 6.2, DutyStatus::OffDuty),
+            segment(6.2, 6.3, DutyStatus::Driving),
+            segment(6.3, 24.0, DutyStatus::OnDuty),
+        ];
+        let grid = status_grid(&log);
+        assert_eq!(grid[24], Some(DutyStatus::OffDuty));
+        assert_eq!(grid[25], Some(DutyStatus::OnDuty));
+    }
+
+    #[test]
+    fn bucket_statuses_split_the_day_into_buckets() {
+        let log = [segment(0.0, 24.0, DutyStatus::OffDuty)];
+        assert_eq!(bucket_statuses(&log, 15).len(), 96);
+        assert_eq!(bucket_statuses(&log, 60).len(), 24);
+        assert_eq!(bucket_statuses(&log, 7).len(), 206);
+        assert!(bucket_statuses(&log, 0).is_empty());
+        assert_eq!(bucket_statuses(&log, 15), status_grid(&log).to_vec());
+    }
+
+    #[test]
+    fn bucket_statuses_take_the_dominant_status_and_leave_gaps_empty() {
+        let log = [
+            segment(0.0, 6.25, DutyStatus::OffDuty),
+            segment(6.25, 10.0, DutyStatus::Driving),
+            segment(12.0, 24.0, DutyStatus::OnDuty),
+        ];
+        let buckets = bucket_statuses(&log, 60);
+        assert_eq!(buckets[5], Some(DutyStatus::OffDuty));
+        assert_eq!(buckets[6], Some(DutyStatus::Driving));
+        assert_eq!(buckets[10], None);
+        assert_eq!(buckets[11], None);
+        assert_eq!(buckets[12], Some(DutyStatus::OnDuty));
+    }
+
+    #[test]
+    fn longest_segment_per_status_is_not_a_total() {
+        let log = [
+            segment(0.0, 6.0, DutyStatus::OffDuty),
+            segment(6.0, 9.0, DutyStatus::Driving),
+            segment(9.0, 10.0, DutyStatus::OffDuty),
+            segment(10.0, 15.0, DutyStatus::Driving),
+        ];
+        let longest = longest_segment_per_status(&log);
+        assert_eq!(longest[DutyStatus::Driving.index()], 5.0);
+        assert_eq!(longest[DutyStatus::OffDuty.index()], 6.0);
+        assert_eq!(longest[DutyStatus::Sleeper.index()], 0.0);
+    }
+
+    #[test]
+    fn a_segment_ending_at_24_covers_the_end_of_the_day() {
+        let log = [
+            segment(0.0, 20.0, DutyStatus::OffDuty),
+            segment(20.0, 24.0, DutyStatus::Sleeper),
+        ];
+        assert!(find_gaps(&log).is_empty());
+        assert_eq!(status_at(&log, 23.99), Some(DutyStatus::Sleeper));
+        assert_eq!(status_at(&log, 24.0), Some(DutyStatus::Sleeper));
+        assert_eq!(status_at(&log, 20.0), Some(DutyStatus::Sleeper));
+    }
+
+    #[test]
+    fn status_at_is_none_in_a_gap() {
+        let log = [segment(0.0, 10.0, DutyStatus::OffDuty)];
+        assert_eq!(status_at(&log, 10.0), None);
+        assert_eq!(status_at(&log, 24.0), None);
+    }
+
+    #[test]
+    fn gap_percentage_measures_unaccounted_time() {
+        assert_eq!(
+            gap_percentage(&[segment(0.0, 24.0, DutyStatus::OffDuty)]),
+            0.0
+        );
+        assert_eq!(
+            gap_percentage(&[segment(0.0, 18.0, DutyStatus::OffDuty)]),
+            0.25
+        );
+        assert_eq!(gap_percentage(&[]), 1.0);
+    }
+
+    #[test]
+    fn find_gaps_lists_uncovered_hours() {
+        let log = [
+            segment(0.0, 10.0, DutyStatus::OffDuty),
+            segment(12.0, 20.0, DutyStatus::Driving),
+        ];
+        assert_eq!(find_gaps(&log), vec![(10.0, 12.0), (20.0, 24.0)]);
+        assert_eq!(find_gaps(&[]), vec![(0.0, 24.0)]);
+        assert!(find_gaps(&[segment(0.0, 24.0, DutyStatus::OffDuty)]).is_empty());
+    }
+
+    #[test]
+    fn find_gaps_merges_overlapping_segments() {
+        let log = [
+            segment(8.0, 12.0, DutyStatus::Driving),
+            segment(0.0, 10.0, DutyStatus::OffDuty),
+            segment(9.0, 11.0, DutyStatus::OnDuty),
+            segment(14.0, 24.0, DutyStatus::OffDuty),
+        ];
+        assert_eq!(find_gaps(&log), vec![(12.0, 14.0)]);
+    }
+
+    #[test]
+    fn fill_gaps_inserts_the_given_status() {
+        let mut log = vec![
+            segment(12.0, 24.0, DutyStatus::Driving),
+            segment(0.0, 10.0, DutyStatus::OffDuty),
+        ];
+        fill_gaps(&mut log, DutyStatus::OffDuty);
+        assert_eq!(
+            log,
+            vec![
+                segment(0.0, 10.0, DutyStatus::OffDuty),
+                segment(10.0, 12.0, DutyStatus::OffDuty),
+                segment(12.0, 24.0, DutyStatus::Driving),
+            ]
+        );
+
+        let full = log.clone();
+        fill_gaps(&mut log, DutyStatus::OnDuty);
+        assert_eq!(log, full);
+    }
+
+    #[test]
+    fn first_gap_of_at_least_skips_gaps_that_are_too_short() {
+        let log = [
+            segment(0.0, 8.0, DutyStatus::OffDuty),
+            segment(8.5, 12.0, DutyStatus::Driving),
+            segment(14.0, 24.0, DutyStatus::OffDuty),
+        ];
+        assert_eq!(first_gap_of_at_least(&log, 0.5), Some((8.0, 8.5)));
+        assert_eq!(first_gap_of_at_least(&log, 1.0), Some((12.0, 14.0)));
+        assert_eq!(first_gap_of_at_least(&log, 3.0), None);
+    }
+
+    #[test]
+    fn status_centroid_weights_midpoints_by_duration() {
+        let log = [
+            segment(0.0, 6.0, DutyStatus::OffDuty),
+            segment(6.0, 8.0, DutyStatus::Driving),
+            segment(14.0, 16.0, DutyStatus::Driving),
+        ];
+        assert_eq!(status_centroid(&log, &DutyStatus::Driving), Some(11.0));
+        assert_eq!(status_centroid(&log, &DutyStatus::Sleeper), None);
+    }
+
+    #[test]
+    fn fit_to_span_handles_each_overflow_mode() {
+        let log = [segment(20.0, 26.0, DutyStatus::Driving)];
+        let props = |on_overflow| ChartProps {
+            on_overflow,
+            ..ChartProps::default()
+        };
+
+        assert_eq!(
+            fit_to_span(&log, &props(OverflowMode::Clip)),
+            (vec![segment(20.0, 24.0, DutyStatus::Driving)], 24.0)
+        );
+        assert_eq!(
+            fit_to_span(&log, &props(OverflowMode::Wrap)),
+            (
+                vec![
+                    segment(20.0, 24.0, DutyStatus::Driving),
+                    segment(0.0, 2.0, DutyStatus::Driving),
+                ],
+                24.0
+            )
+        );
+        assert_eq!(
+            fit_to_span(&log, &props(OverflowMode::Extend)),
+            (log.to_vec(), 26.0)
+        );
+    }
+
+    #[test]
+    fn validate_segments_reports_every_problem() {
+        assert_eq!(
+            validate_segments(&[
+                segment(0.0, 6.0, DutyStatus::OffDuty),
+                segment(6.0, 24.0, DutyStatus::Driving),
+            ]),
+            Ok(())
+        );
+
+        let errors = validate_segments(&[
+            segment(0.0, 8.0, DutyStatus::OffDuty),
+            segment(6.0, 10.0, DutyStatus::Driving),
+            segment(12.0, 11.0, DutyStatus::OnDuty),
+            segment(2.0, 4.0, DutyStatus::Sleeper),
+            segment(20.0, 25.0, DutyStatus::OffDuty),
+        ])
+        .unwrap_err();
+        assert_eq!(
+            errors,
+            vec![
+                SegmentError {
+                    kind: SegmentErrorKind::Overlap,
+                    index: 1,
+                    other: Some(0),
+                },
+                SegmentError {
+                    kind: SegmentErrorKind::Reversed,
+                    index: 2,
+                    other: None,
+                },
+                SegmentError {
+                    kind: SegmentErrorKind::Overlap,
+                    index: 3,
+                    other: Some(0),
+                },
+                SegmentError {
+                    kind: SegmentErrorKind::OutOfOrder,
+                    index: 3,
+                    other: Some(2),
+                },
+                SegmentError {
+                    kind: SegmentErrorKind::OutOfBounds,
+                    index: 4,
+                    other: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn segment_at_finds_the_segment_under_a_point() {
+        let props = ChartProps {
+            width: 860,
+            ..ChartProps::default()
+        };
+        let log = [
+            segment(0.0, 6.0, DutyStatus::OffDuty),
+            segment(6.0, 12.0, DutyStatus::Driving),
+            segment(12.0, 24.0, DutyStatus::OnDuty),
+        ];
+        // 30px per hour from x = 70.
+        assert_eq!(segment_at(&log, &props, 300.0, 177.5), Some(1));
+        assert_eq!(segment_at(&log, &props, 520.0, 232.5), Some(2));
+        assert_eq!(segment_at(&log, &props, 100.0, 67.5), Some(0));
+        assert_eq!(segment_at(&log, &props, 300.0, 67.5), None);
+        assert_eq!(segment_at(&log, &props, 300.0, 150.0), None);
+        assert_eq!(segment_at(&log, &props, 300.0, 10.0), None);
+    }
+
+    #[test]
+    fn fill_end_of_day_pads_with_off_duty_by_default() {
+        let log = [
+            segment(0.0, 6.0, DutyStatus::OffDuty),
+            segment(6.0, 20.0, DutyStatus::Driving),
+        ];
+        let filled = fill_end_of_day(&log, &ChartProps::default());
+        assert_eq!(filled[2], segment(20.0, 24.0, DutyStatus::OffDuty));
+        assert!(find_gaps(&filled).is_empty());
+    }
+
+    #[test]
+    fn fill_end_of_day_uses_the_configured_status() {
+        let log = [
+            segment(0.0, 6.0, DutyStatus::OffDuty),
+            segment(6.0, 20.0, DutyStatus::Driving),
+        ];
+        let props = ChartProps {
+            end_of_day_status: Some(DutyStatus::Sleeper),
+            ..ChartProps::default()
+        };
+        assert_eq!(
+            fill_end_of_day(&log, &props)[2],
+            segment(20.0, 24.0, DutyStatus::Sleeper)
+        );
+
+        let extended = fill_end_of_day(&[segment(0.0, 20.0, DutyStatus::Sleeper)], &props);
+        assert_eq!(extended, vec![segment(0.0, 24.0, DutyStatus::Sleeper)]);
+    }
+
+    #[test]
+    fn fill_end_of_day_can_be_turned_off() {
+        let log = [
+            segment(0.0, 6.0, DutyStatus::OffDuty),
+            segment(6.0, 20.0, DutyStatus::Driving),
+        ];
+        let props = ChartProps {
+            end_of_day_status: None,
+            ..ChartProps::default()
+        };
+        assert_eq!(fill_end_of_day(&log, &props), log.to_vec());
+    }
+
+    #[test]
+    fn sample_day_is_a_valid_compliant_full_day() {
+        let day = sample_day();
+        assert_eq!(validate_segments(&day), Ok(()));
+        assert!(find_gaps(&day).is_empty());
+        assert_eq!(day.first().unwrap().start_hour, 0.0);
+        assert_eq!(day.last().unwrap().end_hour, 24.0);
+        assert!(crate::hos::check_hos(&day).is_empty());
+    }
+
+    #[test]
+    fn status_color_pairs_follow_the_props_colors_in_canonical_order() {
+        let props = ChartProps {
+            driving_color: "#00AA00".to_string(),
+            yard_move_color: "#FFD700".to_string(),
+            ..ChartProps::default()
+        };
+        let pairs = status_color_pairs(&props);
+
+        assert_eq!(
+            pairs
+                .iter()
+                .map(|(status, _)| status.clone())
+                .collect::<Vec<_>>(),
+            DutyStatus::ALL.to_vec()
+        );
+        assert_eq!(pairs[2], (DutyStatus::Driving, "#00AA00"));
+        assert_eq!(pairs[5], (DutyStatus::YardMove, "#FFD700"));
+        assert!(pairs
+            .iter()
+            .all(|(status, color)| *color == props.color_for(status)));
+    }
+
+    #[test]
+    fn legend_entries_omit_absent_statuses_only_when_asked() {
+        let log = [
+            segment(0.0, 6.0, DutyStatus::OffDuty),
+            segment(6.0, 7.0, DutyStatus::YardMove),
+            segment(7.0, 24.0, DutyStatus::Driving),
+        ];
+        let props = ChartProps::default();
+        assert_eq!(legend_entries(&log, &props), status_color_pairs(&props));
+
+        let props = ChartProps {
+            legend_active_only: true,
+            ..ChartProps::default()
+        };
+        assert_eq!(
+            legend_entries(&log, &props),
+            vec![
+                (DutyStatus::OffDuty, "#8E8E8E"),
+                (DutyStatus::Driving, "green"),
+                (DutyStatus::YardMove, "goldenrod"),
+            ]
+        );
+        assert!(legend_entries(&[], &props).is_empty());
+    }
+
+    #[test]
+    fn hour_label_font_px_grows_with_the_columns_within_bounds() {
+        let props = ChartProps::default();
+        assert_eq!(hour_label_font_px(900.0, &props), 12.0);
+        // 30-pixel columns give 11.25 pixels, rounded down.
+        assert_eq!(hour_label_font_px(860.0, &props), 11.0);
+        assert_eq!(hour_label_font_px(400.0, &props), MIN_HOUR_LABEL_FONT_PX);
+        assert_eq!(hour_label_font_px(2000.0, &props), MAX_HOUR_LABEL_FONT_PX);
+
+        // Zooming to twelve hours doubles the columns.
+        let zoomed = ChartProps {
+            start_hour: 6.0,
+            end_hour: 18.0,
+            ..ChartProps::default()
+        };
+        assert_eq!(hour_label_font_px(860.0, &zoomed), 23.0);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn chart_props_deserialize_missing_fields_to_their_defaults() {
+        let props: ChartProps = serde_json::from_str(r##"{"driving_color": "#00AA00"}"##).unwrap();
+
+        assert_eq!(props.driving_color, "#00AA00");
+        assert!(
+            props
+                == ChartProps {
+                    driving_color: "#00AA00".to_string(),
+                    ..ChartProps::default()
+                }
+        );
+    }
 }
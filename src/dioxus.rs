@@ -1,9 +1,25 @@
 #![doc = include_str!("../DIOXUS.md")]
 
+use crate::chart::container_class;
+use crate::chart::effective_width;
+use crate::chart::forget_grid;
+use crate::chart::redraw_base;
+use crate::chart::segment_at;
+use crate::chart::segment_tooltip;
+use crate::chart::Annotation;
 use crate::chart::ChartProps;
+use crate::chart::ChartStyle;
+use crate::chart::DutyStatus;
+use crate::chart::GridMode;
 use crate::chart::Segment;
+use crate::chart::SegmentStyle;
+use crate::chart::{Align, OverflowMode};
 use crate::draw_chart;
+use crate::redraw_chart;
 use dioxus::prelude::*;
+use std::borrow::Cow;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
 
 #[derive(Props, PartialEq, Clone)]
 pub struct Properties {
@@ -30,15 +46,15 @@ pub struct Properties {
     ///
     /// Specifies the color used for the chart's background. Accepts any valid CSS color format.
     /// Defaults to `"#FFFFFF"` (white) if not provided.
-    #[props(default = "#FFFFFF")]
-    pub background_color: &'static str,
+    #[props(into, default = Cow::Borrowed("#FFFFFF"))]
+    pub background_color: Cow<'static, str>,
 
     /// The color of the grid lines on the chart.
     ///
     /// Defines the color used for the grid lines. Accepts any valid CSS color format.
     /// Defaults to `"#CCCCCC"` (light gray) if not provided.
-    #[props(default = "#CCCCCC")]
-    pub grid_color: &'static str,
+    #[props(into, default = Cow::Borrowed("#CCCCCC"))]
+    pub grid_color: Cow<'static, str>,
 
     /// The font style used for labels.
     ///
@@ -52,36 +68,329 @@ pub struct Properties {
     ///
     /// Specifies the color used for the text labels on the chart's axes.
     /// Defaults to `"#444444"` (dark gray) if not provided.
-    #[props(default = "#444444")]
-    pub label_color: &'static str,
+    #[props(into, default = Cow::Borrowed("#444444"))]
+    pub label_color: Cow<'static, str>,
 
     /// The color used to represent "Off Duty" status.
     ///
     /// This color is used in the chart to visualize periods when a user is off duty.
     /// Defaults to `"#8E8E8E"` (gray) if not provided.
-    #[props(default = "#8E8E8E")]
-    pub off_duty_color: &'static str,
+    #[props(into, default = Cow::Borrowed("#8E8E8E"))]
+    pub off_duty_color: Cow<'static, str>,
 
     /// The color used to represent "Sleeper" status.
     ///
     /// This color is used in the chart to indicate periods when a user is in a sleeper berth.
     /// Defaults to `black` if not provided.
-    #[props(default = "black")]
-    pub sleeper_color: &'static str,
+    #[props(into, default = Cow::Borrowed("black"))]
+    pub sleeper_color: Cow<'static, str>,
 
     /// The color used to represent "Driving" status.
     ///
     /// This color is used in the chart to highlight periods when a user is actively driving.
     /// Defaults to `green` if not provided.
-    #[props(default = "green")]
-    pub driving_color: &'static str,
+    #[props(into, default = Cow::Borrowed("green"))]
+    pub driving_color: Cow<'static, str>,
 
     /// The color used to represent "On Duty" status.
     ///
     /// This color is used in the chart to indicate periods when a user is on duty but not driving.
     /// Defaults to `orange` if not provided.
-    #[props(default = "orange")]
-    pub on_duty_color: &'static str,
+    #[props(into, default = Cow::Borrowed("orange"))]
+    pub on_duty_color: Cow<'static, str>,
+
+    /// The color used to represent "Personal Conveyance" status.
+    ///
+    /// Defaults to `steelblue` if not provided.
+    #[props(into, default = Cow::Borrowed("steelblue"))]
+    pub personal_conveyance_color: Cow<'static, str>,
+
+    /// The color used to represent "Yard Move" status.
+    ///
+    /// Defaults to `goldenrod` if not provided.
+    #[props(into, default = Cow::Borrowed("goldenrod"))]
+    pub yard_move_color: Cow<'static, str>,
+
+    /// Which vertical grid lines are drawn.
+    ///
+    /// `GridMode::Hourly` draws a line every hour, `GridMode::TransitionsOnly` only where
+    /// a segment starts or ends, and `GridMode::None` omits vertical lines entirely.
+    /// Defaults to `GridMode::Hourly` if not provided.
+    #[props(default)]
+    pub grid_mode: GridMode,
+
+    /// Whether the status rows are drawn in reverse order.
+    ///
+    /// When `true`, On Duty is drawn at the top and Off Duty at the bottom.
+    /// Defaults to `false` if not provided.
+    #[props(default = false)]
+    pub flip_y: bool,
+
+    /// Labeled markers drawn at specific `(hour, status)` points.
+    ///
+    /// Useful for instantaneous events such as inspections. Defaults to no annotations.
+    #[props(default)]
+    pub annotations: Vec<Annotation>,
+
+    /// Text drawn at the bottom-right corner of the canvas.
+    ///
+    /// Use `eld::chart::generated_footer()` to stamp printed reports with the
+    /// generation time. Defaults to `None` (no footer).
+    #[props(default)]
+    pub footer: Option<String>,
+
+    /// The radius, in pixels, of the chart's rounded corners.
+    ///
+    /// Drawing is clipped to a rounded rectangle when greater than `0.0`; pair it with
+    /// a `border-radius` in `canvas_style`. Defaults to `0.0` (square corners).
+    #[props(default = 0.0)]
+    pub corner_radius: f64,
+
+    /// How the duty status segments are visualized.
+    ///
+    /// `ChartStyle::GridLines` draws a line per segment in its status row, while
+    /// `ChartStyle::Ribbon` fills a full-height band per segment.
+    /// Defaults to `ChartStyle::GridLines` if not provided.
+    #[props(default)]
+    pub style: ChartStyle,
+
+    /// Whether all coordinates are rounded to whole pixels.
+    ///
+    /// Produces crisp lines for pixel-perfect exports. Defaults to `false` if not provided.
+    #[props(default = false)]
+    pub integer_layout: bool,
+
+    /// Whether split-sleeper rest pairs are linked with a bracket below the grid.
+    ///
+    /// Defaults to `false` if not provided.
+    #[props(default = false)]
+    pub split_sleeper_brackets: bool,
+
+    /// The horizontal range, in canvas pixels, that is currently visible.
+    ///
+    /// Segments entirely outside this range are not drawn, which keeps dense logs fast.
+    /// Defaults to `None` (draw every segment).
+    #[props(default)]
+    pub visible_range: Option<(f64, f64)>,
+
+    /// The number of hours covered by the time axis.
+    ///
+    /// Defaults to `24.0` (one day) if not provided.
+    #[props(default = 24.0)]
+    pub hours_span: f32,
+
+    /// How segments running past `hours_span` are drawn.
+    ///
+    /// `OverflowMode::Clip` cuts them at the edge, `OverflowMode::Wrap` continues them
+    /// from the start, and `OverflowMode::Extend` grows the time axis.
+    /// Defaults to `OverflowMode::Clip` if not provided.
+    #[props(default)]
+    pub on_overflow: OverflowMode,
+
+    /// Relative heights of the Off Duty, Sleeper, Driving, and On Duty rows.
+    ///
+    /// For example `Some([1.0, 1.0, 2.0, 1.0])` doubles the Driving row.
+    /// Defaults to `None` (equal heights) if not provided.
+    #[props(default)]
+    pub row_heights: Option<[f64; 4]>,
+
+    /// A vertical background gradient, as `(top_color, bottom_color)`.
+    ///
+    /// Covers the flat `background_color` when set. Defaults to `None` if not provided.
+    #[props(default)]
+    pub background_gradient: Option<(String, String)>,
+
+    /// The night hours, as `(start, end)`, tinted behind the segments.
+    ///
+    /// Wraps past midnight when start is after end. Defaults to `None` (no tint).
+    #[props(default)]
+    pub night_window: Option<(f32, f32)>,
+
+    /// The translucent color used to tint the night hours.
+    ///
+    /// Defaults to `"rgba(25, 25, 112, 0.1)"` (faint midnight blue) if not provided.
+    #[props(into, default = Cow::Borrowed("rgba(25, 25, 112, 0.1)"))]
+    pub night_tint_color: Cow<'static, str>,
+
+    /// Whether a summary panel is drawn to the right of the grid.
+    ///
+    /// The panel shows a swatch, label, and total hours for each duty status.
+    /// Defaults to `false` if not provided.
+    #[props(default = false)]
+    pub side_panel: bool,
+
+    /// How each segment is drawn within its status row.
+    ///
+    /// `SegmentStyle::Line` draws a thin line, while `SegmentStyle::Pill` draws a rounded
+    /// bar `pill_height` pixels tall. Only applies to `ChartStyle::GridLines`.
+    /// Defaults to `SegmentStyle::Line` if not provided.
+    #[props(default)]
+    pub segment_style: SegmentStyle,
+
+    /// The height, in pixels, of each bar in `SegmentStyle::Pill` mode.
+    ///
+    /// Defaults to `12.0` if not provided.
+    #[props(default = 12.0)]
+    pub pill_height: f64,
+
+    /// Whether driving beyond the 11-hour limit is drawn in `overage_color`.
+    ///
+    /// Defaults to `false` if not provided.
+    #[props(default = false)]
+    pub highlight_overage: bool,
+
+    /// The color of driving time past the 11-hour limit.
+    ///
+    /// Defaults to `"red"` if not provided.
+    #[props(into, default = Cow::Borrowed("red"))]
+    pub overage_color: Cow<'static, str>,
+
+    /// The minimum drawn width, in pixels, of each segment.
+    ///
+    /// Keeps very short segments visible by widening them around their center.
+    /// Defaults to `0.0` (true widths) if not provided.
+    #[props(default = 0.0)]
+    pub min_segment_px: f64,
+
+    /// Whether the row labels are abbreviated ("OFF", "SB", "D", "ON").
+    ///
+    /// Defaults to `false` (full status names) if not provided.
+    #[props(default = false)]
+    pub compact_labels: bool,
+
+    /// The `id` of the `<canvas>` element.
+    ///
+    /// Give each chart on a page its own id so they do not draw into the same canvas.
+    /// The container `div` gets the id `"{canvas_id}-container"`.
+    /// Defaults to `"eld-canvas"` if not provided.
+    #[props(default = "eld-canvas")]
+    pub canvas_id: &'static str,
+
+    /// Whether the legend in the side panel only lists statuses present in the data.
+    ///
+    /// Defaults to `false` (all statuses) if not provided.
+    #[props(default = false)]
+    pub legend_active_only: bool,
+
+    /// The smallest width, in pixels, the chart may shrink to.
+    ///
+    /// Setting `min_width` or `max_width` makes the chart fill its container within
+    /// those bounds instead of using the fixed `width`. Defaults to `None` if not provided.
+    #[props(default)]
+    pub min_width: Option<u32>,
+
+    /// The largest width, in pixels, the chart may grow to.
+    ///
+    /// Defaults to `None` if not provided.
+    #[props(default)]
+    pub max_width: Option<u32>,
+
+    /// The status assumed after the last segment until the end of the day.
+    ///
+    /// Defaults to `Some(DutyStatus::OffDuty)` if not provided; `None` leaves the rest of
+    /// the day blank.
+    #[props(default = Some(DutyStatus::OffDuty))]
+    pub end_of_day_status: Option<DutyStatus>,
+
+    /// A border stroked around each pill or ribbon segment, as `(color, line_width)`.
+    ///
+    /// Defaults to `None` (no border) if not provided.
+    #[props(default)]
+    pub segment_border: Option<(String, f64)>,
+
+    /// The first hour shown on the time axis.
+    ///
+    /// Together with `end_hour`, zooms the chart into part of the day, e.g. a
+    /// single shift. Defaults to `0.0` if not provided.
+    #[props(default = 0.0)]
+    pub start_hour: f32,
+
+    /// The last hour shown on the time axis.
+    ///
+    /// Defaults to `24.0` (the end of `hours_span`) if not provided.
+    #[props(default = 24.0)]
+    pub end_hour: f32,
+
+    /// Whether the odometer reading of each status change is drawn below the grid.
+    ///
+    /// Defaults to `false` if not provided.
+    #[props(default = false)]
+    pub show_odometer: bool,
+
+    /// Where the chart is placed on a canvas wider than `max_width`.
+    ///
+    /// Defaults to `Align::Left` if not provided.
+    #[props(default = Align::Left)]
+    pub align: Align,
+
+    /// The current hour of the day, marked with a vertical line labeled "NOW".
+    ///
+    /// Defaults to `None` (no marker) if not provided.
+    #[props(default)]
+    pub now_hour: Option<f32>,
+
+    /// The color of the `now_hour` marker.
+    ///
+    /// Defaults to `"#E53935"` if not provided.
+    #[props(into, default = Cow::Borrowed("#E53935"))]
+    pub now_color: Cow<'static, str>,
+
+    /// Inline style applied to the container `div` wrapping the canvas.
+    ///
+    /// Defaults to `"position: relative; max-width: 100%; overflow-x: auto;"`, which makes
+    /// the chart scroll horizontally inside narrow layouts.
+    #[props(default = "position: relative; max-width: 100%; overflow-x: auto;")]
+    pub container_style: &'static str,
+
+    /// CSS class applied to the container `div` wrapping the canvas.
+    ///
+    /// Defaults to an empty string (no class).
+    #[props(default = "")]
+    pub container_class: &'static str,
+
+    /// Inline style applied to the `<canvas>` element.
+    ///
+    /// The `background_color` and a cursor matching `interactive` are always appended
+    /// to this style. Defaults to `"border: 1px solid black;"` if not provided.
+    #[props(default = "border: 1px solid black;")]
+    pub canvas_style: &'static str,
+
+    /// CSS class applied to the `<canvas>` element.
+    ///
+    /// Defaults to an empty string (no class).
+    #[props(default = "")]
+    pub canvas_class: &'static str,
+
+    /// Whether the chart reacts to user input.
+    ///
+    /// When `false`, the chart is a read-only view: no event listeners are attached
+    /// to the canvas and the cursor stays the default arrow instead of a pointer.
+    /// Defaults to `true` if not provided.
+    #[props(default = true)]
+    pub interactive: bool,
+
+    /// Called with the container's horizontal scroll offset as the user scrolls.
+    ///
+    /// The offset is the container's `scrollLeft` in pixels, which makes it easy to
+    /// keep a detail panel in sync with the visible part of the chart.
+    /// Defaults to `None` (no callback).
+    #[props(default)]
+    pub on_scroll: Option<EventHandler<f64>>,
+
+    /// Called with the index, in `data`, of the segment the user clicks on.
+    ///
+    /// Clicks are mapped to segments with `segment_at`, so clicks on empty space or on
+    /// the lines between rows are ignored. Only fires when `interactive` is `true`.
+    /// Defaults to `None` (no callback).
+    #[props(default)]
+    pub on_segment_click: Option<EventHandler<usize>>,
+
+    /// Called with the cumulative number of redraws each time the chart is redrawn.
+    ///
+    /// Meant for diagnosing excessive rerenders, so it only fires in debug builds.
+    /// Defaults to `None` (no callback).
+    #[props(default)]
+    pub on_redraw: Option<EventHandler<u32>>,
 }
 
 /// Chart Component
@@ -104,6 +413,50 @@ pub struct Properties {
 /// - **sleeper_color** *(String)* - The color representing **Sleeper Berth** time.
 /// - **driving_color** *(String)* - The color representing **Driving** time.
 /// - **on_duty_color** *(String)* - The color representing **On Duty** time.
+/// - **personal_conveyance_color** *(String)* - The color representing **Personal Conveyance** time.
+/// - **yard_move_color** *(String)* - The color representing **Yard Move** time.
+/// - **grid_mode** *(GridMode)* - Which vertical grid lines are drawn.
+/// - **flip_y** *(bool)* - Whether the status rows are drawn bottom-up.
+/// - **annotations** *(Vec<Annotation>)* - Labeled markers drawn at specific points.
+/// - **footer** *(Option<String>)* - Text drawn at the bottom-right corner of the canvas.
+/// - **corner_radius** *(f64)* - The radius of the chart's rounded corners.
+/// - **style** *(ChartStyle)* - Whether segments are drawn as row lines or a ribbon.
+/// - **integer_layout** *(bool)* - Whether coordinates are rounded to whole pixels.
+/// - **split_sleeper_brackets** *(bool)* - Whether split-sleeper pairs are bracketed.
+/// - **visible_range** *(Option<(f64, f64)>)* - The visible pixel range; other segments are skipped.
+/// - **hours_span** *(f32)* - The number of hours covered by the time axis.
+/// - **on_overflow** *(OverflowMode)* - How segments past `hours_span` are drawn.
+/// - **row_heights** *(Option<[f64; 4]>)* - Relative heights of the four status rows.
+/// - **background_gradient** *(Option<(String, String)>)* - A top-to-bottom background gradient.
+/// - **night_window** *(Option<(f32, f32)>)* - The night hours tinted behind the segments.
+/// - **night_tint_color** *(String)* - The color of the night tint.
+/// - **side_panel** *(bool)* - Whether a swatch, label, and total per status is drawn on the right.
+/// - **segment_style** *(SegmentStyle)* - Whether segments are drawn as lines or rounded pills.
+/// - **pill_height** *(f64)* - The height of each pill in `SegmentStyle::Pill` mode.
+/// - **highlight_overage** *(bool)* - Whether driving past the 11-hour limit is highlighted.
+/// - **overage_color** *(String)* - The color of driving past the 11-hour limit.
+/// - **min_segment_px** *(f64)* - The minimum drawn width of each segment.
+/// - **compact_labels** *(bool)* - Whether the row labels are abbreviated.
+/// - **canvas_id** *(String)* - The `id` of the `<canvas>` element.
+/// - **legend_active_only** *(bool)* - Whether the legend only lists statuses present in the data.
+/// - **min_width** *(Option<u32>)* - The smallest width of a responsive chart.
+/// - **max_width** *(Option<u32>)* - The largest width of a responsive chart.
+/// - **end_of_day_status** *(Option<DutyStatus>)* - The status filled in after the last segment until hour 24.
+/// - **segment_border** *(Option<(String, f64)>)* - A border around each pill or ribbon segment.
+/// - **start_hour** *(f32)* - The first hour shown on the time axis.
+/// - **end_hour** *(f32)* - The last hour shown on the time axis.
+/// - **show_odometer** *(bool)* - Whether odometer readings are drawn at status changes.
+/// - **align** *(Align)* - Where the chart is placed on a canvas wider than `max_width`.
+/// - **now_hour** *(Option<f32>)* - The current hour, marked with a "NOW" line.
+/// - **now_color** *(String)* - The color of the `now_hour` marker.
+/// - **container_style** *(String)* - Inline style of the container `div`.
+/// - **container_class** *(String)* - CSS class of the container `div`.
+/// - **canvas_style** *(String)* - Inline style of the `<canvas>` element.
+/// - **canvas_class** *(String)* - CSS class of the `<canvas>` element.
+/// - **interactive** *(bool)* - Whether the chart reacts to user input.
+/// - **on_scroll** *(Option<EventHandler<f64>>)* - Called with the container's scroll offset in pixels.
+/// - **on_segment_click** *(Option<EventHandler<usize>>)* - Called with the index of the clicked segment.
+/// - **on_redraw** *(Option<EventHandler<u32>>)* - Called with the cumulative redraw count (debug builds only).
 ///
 /// # Examples
 ///
@@ -112,17 +465,12 @@ pub struct Properties {
 ///
 /// ```rust
 /// use dioxus::prelude::*;
-/// use eld::{Segment, DutyStatus};
+/// use eld::chart::sample_day;
 /// use eld::dioxus::Chart;
 ///
 /// #[component]
 /// fn App() -> Element {
-///     let eld_data = use_signal(|| vec![
-///         Segment { start_hour: 0.0, end_hour: 6.0, status: DutyStatus::OffDuty, location: "".to_string(), note: "".to_string() },
-///         Segment { start_hour: 6.0, end_hour: 12.0, status: DutyStatus::Sleeper, location: "".to_string(), note: "".to_string() },
-///         Segment { start_hour: 12.0, end_hour: 18.0, status: DutyStatus::Driving, location: "".to_string(), note: "".to_string() },
-///         Segment { start_hour: 18.0, end_hour: 24.0, status: DutyStatus::OnDuty, location: "".to_string(), note: "".to_string() },
-///     ]);
+///     let eld_data = use_signal(sample_day);
 ///
 ///     rsx! {
 ///         Chart {
@@ -144,47 +492,211 @@ pub struct Properties {
 ///
 /// # Behavior
 /// - When `data` changes, the `use_effect` hook **redraws the chart**.
-/// - The chart is **scrollable horizontally** for better visibility on smaller screens.
+/// - The grid is only redrawn when the canvas size, pixel ratio, or grid settings change, and is
+///   forgotten when the component unmounts so a remounted chart draws it again.
+/// - The chart is **scrollable horizontally** for better visibility on smaller screens,
+///   unless `container_style` overrides the default container style.
+/// - When `interactive`, hovering a segment shows a tooltip with its status, time range,
+///   location, and note (see `segment_tooltip`), hidden again when the cursor leaves.
 /// - Uses the `draw_chart` function to render the chart inside the `<canvas>` element.
 ///
 /// # Notes
-/// - The `<canvas>` must have a unique `id` (`eld-canvas`) for proper rendering.
+/// - The `<canvas>` must have a unique `id` (`canvas_id`, `eld-canvas` by default) for proper rendering.
 /// - The `draw_chart` function must be implemented separately and handle the drawing logic.
 #[component]
 pub fn Chart(props: Properties) -> Element {
-    let data = props.data.clone();
+    let data = props.data;
     let hook_props = props.clone();
+    let redraws = use_hook(|| Rc::new(Cell::new(0_u32)));
+    let drawn_props = use_hook(|| Rc::new(RefCell::new(None::<ChartProps>)));
+    let drawn_segments = use_hook(|| Rc::new(RefCell::new(None::<Vec<Segment>>)));
+    let clicked_props = drawn_props.clone();
+    let hovered_props = drawn_props.clone();
+    let mut tooltip = use_signal(|| None::<(String, f64, f64)>);
+    let canvas_id = props.canvas_id;
+    use_drop(move || forget_grid(canvas_id));
 
     use_effect(move || {
-        if let Err(err) = draw_chart(
-            &data(),
-            &ChartProps {
-                width: hook_props.width,
-                height: hook_props.height,
-                background_color: hook_props.background_color,
-                grid_color: hook_props.grid_color,
-                font: hook_props.font,
-                label_color: hook_props.label_color,
-                off_duty_color: hook_props.off_duty_color,
-                sleeper_color: hook_props.sleeper_color,
-                driving_color: hook_props.driving_color,
-                on_duty_color: hook_props.on_duty_color,
-            },
+        let chart_props = ChartProps {
+            width: hook_props.width,
+            height: hook_props.height,
+            background_color: hook_props.background_color.to_string(),
+            grid_color: hook_props.grid_color.to_string(),
+            font: hook_props.font.to_string(),
+            label_color: hook_props.label_color.to_string(),
+            off_duty_color: hook_props.off_duty_color.to_string(),
+            sleeper_color: hook_props.sleeper_color.to_string(),
+            driving_color: hook_props.driving_color.to_string(),
+            on_duty_color: hook_props.on_duty_color.to_string(),
+            personal_conveyance_color: hook_props.personal_conveyance_color.to_string(),
+            yard_move_color: hook_props.yard_move_color.to_string(),
+            grid_mode: hook_props.grid_mode,
+            flip_y: hook_props.flip_y,
+            annotations: hook_props.annotations.clone(),
+            footer: hook_props.footer.clone(),
+            corner_radius: hook_props.corner_radius,
+            style: hook_props.style,
+            integer_layout: hook_props.integer_layout,
+            split_sleeper_brackets: hook_props.split_sleeper_brackets,
+            visible_range: hook_props.visible_range,
+            hours_span: hook_props.hours_span,
+            on_overflow: hook_props.on_overflow,
+            row_heights: hook_props.row_heights,
+            background_gradient: hook_props.background_gradient.clone(),
+            night_window: hook_props.night_window,
+            night_tint_color: hook_props.night_tint_color.to_string(),
+            side_panel: hook_props.side_panel,
+            segment_style: hook_props.segment_style,
+            pill_height: hook_props.pill_height,
+            highlight_overage: hook_props.highlight_overage,
+            overage_color: hook_props.overage_color.to_string(),
+            min_segment_px: hook_props.min_segment_px,
+            compact_labels: hook_props.compact_labels,
+            canvas_id: hook_props.canvas_id.to_string(),
+            legend_active_only: hook_props.legend_active_only,
+            min_width: hook_props.min_width,
+            max_width: hook_props.max_width,
+            end_of_day_status: hook_props.end_of_day_status.clone(),
+            segment_border: hook_props.segment_border.clone(),
+            start_hour: hook_props.start_hour,
+            end_hour: hook_props.end_hour,
+            show_odometer: hook_props.show_odometer,
+            align: hook_props.align,
+            now_hour: hook_props.now_hour,
+            now_color: hook_props.now_color.to_string(),
+            // Only used by `draw_team`, which the component does not draw.
+            driver_b_dash: Vec::new(),
+        };
+        let width = container_width(canvas_id).map_or(chart_props.width, |available| {
+            effective_width(available, &chart_props)
+        });
+        let chart_props = ChartProps {
+            width,
+            ..chart_props
+        };
+
+        let segments = data();
+        // Only the changed segments need repainting while the props stay the same.
+        let previous = drawn_segments.take();
+        let result = match redraw_base(
+            previous.as_deref(),
+            drawn_props.borrow().as_ref(),
+            &chart_props,
         ) {
+            Some(previous) => redraw_chart(previous, &segments, &chart_props),
+            None => draw_chart(&segments, &chart_props),
+        };
+        if let Err(err) = result {
             log::error!("Failed to draw chart: {}", err);
         }
+        drawn_props.replace(Some(chart_props));
+        drawn_segments.replace(Some(segments));
+
+        if let Some(on_redraw) = hook_props.on_redraw.filter(|_| cfg!(debug_assertions)) {
+            redraws.set(redraws.get() + 1);
+            on_redraw.call(redraws.get());
+        }
     });
 
-    rsx! {
-        div {
-            id: "eld-container",
-            style: "position: relative; max-width: 100%; overflow-x: auto;",
+    let cursor = if props.interactive {
+        "pointer"
+    } else {
+        "default"
+    };
+
+    let chart = rsx! {
+        if props.interactive {
             canvas {
-                id: "eld-canvas",
-                width: "{props.width}",
-                height: "{props.height}",
-                style: "border: 1px solid black; cursor: pointer; background-color: {props.background_color};"
+                id: props.canvas_id,
+                class: props.canvas_class,
+                style: "{props.canvas_style} cursor: {cursor}; background-color: {props.background_color};",
+                onclick: move |event: MouseEvent| {
+                    let Some(on_segment_click) = props.on_segment_click else {
+                        return;
+                    };
+                    let Some(chart_props) = clicked_props.borrow().clone() else {
+                        return;
+                    };
+                    let point = event.element_coordinates();
+                    if let Some(index) = segment_at(&data.peek(), &chart_props, point.x, point.y) {
+                        on_segment_click.call(index);
+                    }
+                },
+                onmousemove: move |event: MouseEvent| {
+                    let point = event.element_coordinates();
+                    let segments = data.peek();
+                    let hovered = hovered_props
+                        .borrow()
+                        .as_ref()
+                        .and_then(|chart_props| segment_at(&segments, chart_props, point.x, point.y));
+                    tooltip.set(hovered.map(|index| {
+                        (
+                            segment_tooltip(&segments[index]),
+                            point.x + TOOLTIP_OFFSET,
+                            point.y + TOOLTIP_OFFSET,
+                        )
+                    }));
+                },
+                onmouseleave: move |_| tooltip.set(None)
+            }
+        } else {
+            canvas {
+                id: props.canvas_id,
+                class: props.canvas_class,
+                style: "{props.canvas_style} cursor: {cursor}; background-color: {props.background_color};"
+            }
+        }
+        if let Some((text, left, top)) = tooltip() {
+            div {
+                class: "eld-tooltip",
+                style: "{TOOLTIP_STYLE} left: {left}px; top: {top}px;",
+                "{text}"
             }
         }
+    };
+
+    // Only attach a scroll listener when an `on_scroll` handler is given.
+    match props.on_scroll {
+        Some(on_scroll) => rsx! {
+            div {
+                id: "{props.canvas_id}-container",
+                class: container_class(props.container_class),
+                style: props.container_style,
+                onscroll: move |_| on_scroll.call(container_scroll_left(props.canvas_id)),
+                {chart}
+            }
+        },
+        None => rsx! {
+            div {
+                id: "{props.canvas_id}-container",
+                class: container_class(props.container_class),
+                style: props.container_style,
+                {chart}
+            }
+        },
     }
 }
+
+/// Distance, in pixels, between the cursor and the tooltip's top-left corner.
+const TOOLTIP_OFFSET: f64 = 12.0;
+
+/// Inline style of the tooltip shown over the hovered segment; the position is appended.
+const TOOLTIP_STYLE: &str = "position: absolute; pointer-events: none; white-space: pre-line; \
+    background: #ffffff; border: 1px solid #cccccc; border-radius: 4px; padding: 4px 6px; \
+    font: 12px Arial;";
+
+/// Returns the width of the chart container, or `None` if it is not mounted.
+fn container_width(canvas_id: &str) -> Option<f64> {
+    web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.get_element_by_id(&format!("{canvas_id}-container")))
+        .map(|container| container.client_width() as f64)
+}
+
+/// Returns the current `scrollLeft` of the chart container, or `0.0` if it is not mounted.
+fn container_scroll_left(canvas_id: &str) -> f64 {
+    web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.get_element_by_id(&format!("{canvas_id}-container")))
+        .map_or(0.0, |container| container.scroll_left() as f64)
+}
@@ -0,0 +1,224 @@
+//! Conversion between a driver's log and FMCSA ELD event records.
+//!
+//! The records follow the "ELD Event List" section of the ELD output file described in
+//! 49 CFR 395 Subpart B, Appendix A, reduced to the fields a day chart needs:
+//!
+//! ```text
+//! <Event Sequence ID>,<Event Record Status>,<Event Record Origin>,<Event Type>,<Event Code>,<Event Time>
+//! ```
+//!
+//! The sequence ID is hexadecimal and the event time is `HHMMSS`. Duty status changes
+//! use event type `1` with codes `1` (off duty) to `4` (on duty), while personal
+//! conveyance and yard moves use event type `3` with codes `1` and `2`.
+
+use crate::chart::{DutyStatus, Segment};
+use std::fmt::Write;
+
+/// Header line that opens the event section of the output file.
+const EVENT_LIST_HEADER: &str = "ELD Event List:";
+
+/// Event record status for an active record.
+const RECORD_STATUS_ACTIVE: u8 = 1;
+
+/// Event record origin for records entered or edited by the driver.
+const RECORD_ORIGIN_DRIVER: u8 = 2;
+
+/// Converts the log into FMCSA ELD event records.
+///
+/// One event is written each time the duty status changes, in chronological order.
+/// Events only mark the start of a status, so a gap in the log is absorbed by the
+/// status before it when the records are read back.
+///
+/// # Parameters
+/// - `segments`: A slice of `Segment` structs representing the driver's log.
+///
+/// # Returns
+/// - `String`: The event list, starting with the `ELD Event List:` header.
+pub fn to_eld_output(segments: &[Segment]) -> String {
+    let mut sorted: Vec<&Segment> = segments.iter().collect();
+    sorted.sort_by(|a, b| a.start_hour.total_cmp(&b.start_hour));
+
+    let mut output = format!("{EVENT_LIST_HEADER}\n");
+    let mut previous: Option<&DutyStatus> = None;
+    let mut sequence = 1;
+    for segment in sorted {
+        if previous == Some(&segment.status) {
+            continue;
+        }
+        let _ = writeln!(
+            output,
+            "{sequence:X},{RECORD_STATUS_ACTIVE},{RECORD_ORIGIN_DRIVER},{},{},{}",
+            segment.status.fmcsa_event_type(),
+            segment.status.fmcsa_code(),
+            format_event_time(segment.start_hour)
+        );
+        previous = Some(&segment.status);
+        sequence += 1;
+    }
+    output
+}
+
+/// Parses FMCSA ELD event records back into a driver's log.
+///
+/// Each event starts a segment that runs until the next event, and the last one runs
+/// to the end of the day. Blank lines and section headers (lines ending in `:`) are
+/// skipped, so a full event section can be passed as is.
+///
+/// # Parameters
+/// - `csv`: The event records, one per line.
+///
+/// # Returns
+/// - `Ok(Vec<Segment>)`: The reconstructed segments, in chronological order.
+/// - `Err(String)`: If a record is malformed or uses an unknown event type or code.
+pub fn from_eld_output(csv: &str) -> Result<Vec<Segment>, String> {
+    let mut events = Vec::new();
+    for line in csv.lines().map(str::trim) {
+        if line.is_empty() || line.ends_with(':') {
+            continue;
+        }
+        let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+        if fields.len() < 6 {
+            return Err(format!("Expected 6 fields in event record: {line}"));
+        }
+        let status = status_from_event(fields[3], fields[4])
+            .ok_or_else(|| format!("Unknown event type or code in record: {line}"))?;
+        let hour = parse_event_time(fields[5])
+            .ok_or_else(|| format!("Invalid event time in record: {line}"))?;
+        events.push((hour, status));
+    }
+    events.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let ends = events
+        .iter()
+        .skip(1)
+        .map(|(hour, _)| *hour)
+        .chain(std::iter::once(24.0));
+    Ok(events
+        .iter()
+        .zip(ends)
+        .filter(|((start, _), end)| end > start)
+        .map(|((start, status), end)| Segment::builder(*start, end, status.clone()).build())
+        .collect())
+}
+
+/// Returns the duty status recorded by an FMCSA event type and code, if known.
+fn status_from_event(event_type: &str, event_code: &str) -> Option<DutyStatus> {
+    let event_code: u8 = event_code.parse().ok()?;
+    match event_type {
+        "1" => DutyStatus::try_from(event_code).ok(),
+        "3" => DutyStatus::ALL
+            .into_iter()
+            .find(|status| status.fmcsa_event_type() == 3 && status.fmcsa_code() == event_code),
+        _ => None,
+    }
+}
+
+/// Formats an hour of the day as an `HHMMSS` event time.
+fn format_event_time(hour: f32) -> String {
+    let seconds = (hour * 3600.0).round() as u32;
+    format!(
+        "{:02}{:02}{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+/// Parses an `HHMMSS` event time into an hour of the day.
+fn parse_event_time(time: &str) -> Option<f32> {
+    if time.len() != 6 || !time.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+    let hours: u32 = time[0..2].parse().ok()?;
+    let minutes: u32 = time[2..4].parse().ok()?;
+    let seconds: u32 = time[4..6].parse().ok()?;
+    if hours > 23 || minutes > 59 || seconds > 59 {
+        return None;
+    }
+    Some((hours * 3600 + minutes * 60 + seconds) as f32 / 3600.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(start_hour: f32, end_hour: f32, status: DutyStatus) -> Segment {
+        Segment::builder(start_hour, end_hour, status).build()
+    }
+
+    #[test]
+    fn round_trips_a_full_day() {
+        let log = vec![
+            segment(0.0, 6.0, DutyStatus::OffDuty),
+            segment(6.0, 6.5, DutyStatus::OnDuty),
+            segment(6.5, 11.0, DutyStatus::Driving),
+            segment(11.0, 11.5, DutyStatus::YardMove),
+            segment(11.5, 12.25, DutyStatus::PersonalConveyance),
+            segment(12.25, 24.0, DutyStatus::Sleeper),
+        ];
+        assert_eq!(from_eld_output(&to_eld_output(&log)), Ok(log));
+    }
+
+    #[test]
+    fn round_trip_merges_repeated_statuses() {
+        let log = [
+            segment(0.0, 6.0, DutyStatus::OffDuty),
+            segment(6.0, 8.0, DutyStatus::Driving),
+            segment(8.0, 10.0, DutyStatus::Driving),
+            segment(10.0, 24.0, DutyStatus::OffDuty),
+        ];
+        assert_eq!(
+            from_eld_output(&to_eld_output(&log)),
+            Ok(vec![
+                segment(0.0, 6.0, DutyStatus::OffDuty),
+                segment(6.0, 10.0, DutyStatus::Driving),
+                segment(10.0, 24.0, DutyStatus::OffDuty),
+            ])
+        );
+    }
+
+    #[test]
+    fn writes_one_record_per_status_change() {
+        let log = [
+            segment(0.0, 7.5, DutyStatus::OffDuty),
+            segment(7.5, 24.0, DutyStatus::Driving),
+        ];
+        assert_eq!(
+            to_eld_output(&log),
+            "ELD Event List:\n1,1,2,1,1,000000\n2,1,2,1,3,073000\n"
+        );
+    }
+
+    #[test]
+    fn parses_a_hand_written_sample_with_codes_1_to_4() {
+        let sample = "\
+ELD Event List:
+1,1,2,1,1,000000
+2,1,2,1,4,061500
+3,1,2,1,3,064500
+4,1,2,1,2,180000
+";
+        assert_eq!(
+            from_eld_output(sample),
+            Ok(vec![
+                segment(0.0, 6.25, DutyStatus::OffDuty),
+                segment(6.25, 6.75, DutyStatus::OnDuty),
+                segment(6.75, 18.0, DutyStatus::Driving),
+                segment(18.0, 24.0, DutyStatus::Sleeper),
+            ])
+        );
+    }
+
+    #[test]
+    fn runs_the_last_event_to_the_end_of_the_day() {
+        let segments = from_eld_output("1,1,2,1,3,200000").unwrap();
+        assert_eq!(segments, vec![segment(20.0, 24.0, DutyStatus::Driving)]);
+    }
+
+    #[test]
+    fn rejects_malformed_records() {
+        assert!(from_eld_output("1,1,2,1,3").is_err());
+        assert!(from_eld_output("1,1,2,1,9,000000").is_err());
+        assert!(from_eld_output("1,1,2,1,3,250000").is_err());
+    }
+}
@@ -0,0 +1,1413 @@
+//! Hours-of-Service (HOS) helpers computed from a driver's log.
+//!
+//! These functions work on plain `Segment` slices and never touch the DOM, so they can
+//! be used by any frontend or by headless tooling.
+
+use crate::chart::{find_gaps, DutyStatus, Segment};
+use std::fmt;
+
+/// Identifies the Hours-of-Service rule a `HosViolation` breaks.
+///
+/// - `DrivingLimit`: Cumulative driving went past the 11-hour limit.
+/// - `OnDutyWindow`: Driving continued after the 14-hour on-duty window closed.
+/// - `RestBreak`: Driving continued past the trigger without the required break.
+/// - `InsufficientRest`: No rest block was long enough; `longest_hours` is the longest
+///   rest found.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HosRule {
+    DrivingLimit,
+    OnDutyWindow,
+    RestBreak,
+    InsufficientRest { longest_hours: f32 },
+}
+
+impl fmt::Display for HosRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            HosRule::DrivingLimit => write!(f, "11-hour driving limit"),
+            HosRule::OnDutyWindow => write!(f, "14-hour on-duty window"),
+            HosRule::RestBreak => write!(f, "rest break"),
+            HosRule::InsufficientRest { longest_hours } => {
+                write!(f, "required rest (longest rest {:.2}h)", longest_hours)
+            }
+        }
+    }
+}
+
+/// A breach of an Hours-of-Service rule found in a driver's log.
+///
+/// # Fields
+/// - `rule`: The rule that was broken.
+/// - `hour`: The hour at which the rule's threshold was crossed.
+/// - `segment_index`: The index, in the log as given, of the segment crossing the threshold.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HosViolation {
+    pub rule: HosRule,
+    pub hour: f32,
+    pub segment_index: usize,
+}
+
+impl fmt::Display for HosViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} violated at hour {:.2} (segment {})",
+            self.rule, self.hour, self.segment_index
+        )
+    }
+}
+
+impl std::error::Error for HosViolation {}
+
+/// Sums the hours spent actively driving.
+///
+/// Only `DutyStatus::Driving` counts toward the total. Personal conveyance is excluded
+/// even though the driver is behind the wheel: FMCSA treats authorized personal use of
+/// a commercial vehicle as off-duty time, so it never consumes the 11-hour driving limit.
+///
+/// # Parameters
+/// - `segments`: A slice of `Segment` structs representing the driver's log.
+///
+/// # Returns
+/// - `f32`: The total driving hours.
+pub fn driving_hours(segments: &[Segment]) -> f32 {
+    segments
+        .iter()
+        .filter(|segment| segment.status == DutyStatus::Driving)
+        .map(|segment| segment.end_hour - segment.start_hour)
+        .fold(0.0, |total, hours| total + hours)
+}
+
+/// Sums the hours that count as off-duty for rest calculations.
+///
+/// Under the FMCSA guidance on personal conveyance (49 CFR 395.8), moving a commercial
+/// vehicle for personal use is recorded as off-duty time. It therefore counts toward the
+/// driver's rest alongside regular `OffDuty` time.
+///
+/// # Parameters
+/// - `segments`: A slice of `Segment` structs representing the driver's log.
+///
+/// # Returns
+/// - `f32`: The total of `OffDuty` and `PersonalConveyance` hours.
+pub fn off_duty_including_pc(segments: &[Segment]) -> f32 {
+    segments
+        .iter()
+        .filter(|segment| {
+            matches!(
+                segment.status,
+                DutyStatus::OffDuty | DutyStatus::PersonalConveyance
+            )
+        })
+        .map(|segment| segment.end_hour - segment.start_hour)
+        .sum()
+}
+
+/// Cumulative driving hours that trigger the mandatory 30-minute break.
+const BREAK_TRIGGER_HOURS: f32 = 8.0;
+
+/// Minimum length, in hours, of an interruption that satisfies the break rule.
+const BREAK_HOURS: f32 = 0.5;
+
+/// Checks a configurable rest-break rule.
+///
+/// The log is walked in chronological order, accumulating driving time. Consecutive
+/// `OffDuty`, `Sleeper`, and `PersonalConveyance` segments lasting at least
+/// `break_minutes` in total form a qualifying break and reset the counter. Short rest
+/// blocks separated by other duty or by unlogged time do not add up to a break. Driving
+/// past `trigger_driving_hours` without such a break is a violation. This covers the
+/// US rule (30 minutes after 8 hours, see `check_30_minute_break`) as well as EU-style
+/// rules such as 45 minutes after 4.5 hours.
+///
+/// # Parameters
+/// - `segments`: A slice of `Segment` structs representing the driver's log.
+/// - `break_minutes`: The minimum length of a qualifying break, in minutes.
+/// - `trigger_driving_hours`: The driving hours allowed before a break is required.
+///
+/// # Returns
+/// - `Ok(())`: If the driver never drove past the trigger without a break.
+/// - `Err(HosViolation)`: The first point where driving continued past the trigger.
+pub fn check_break_rule(
+    segments: &[Segment],
+    break_minutes: u32,
+    trigger_driving_hours: f32,
+) -> Result<(), HosViolation> {
+    let scan = scan_breaks(segments, break_minutes as f32 / 60.0, trigger_driving_hours);
+    match scan.missed_at {
+        Some((hour, segment_index)) => Err(HosViolation {
+            rule: HosRule::RestBreak,
+            hour,
+            segment_index,
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Checks the US 30-minute break rule: no driving past 8 cumulative hours of driving
+/// without a break of at least 30 minutes.
+///
+/// # Parameters
+/// - `segments`: A slice of `Segment` structs representing the driver's log.
+///
+/// # Returns
+/// - `Ok(())`: If the break rule was respected.
+/// - `Err(HosViolation)`: The first point where driving continued past 8 hours.
+pub fn check_30_minute_break(segments: &[Segment]) -> Result<(), HosViolation> {
+    check_break_rule(segments, (BREAK_HOURS * 60.0) as u32, BREAK_TRIGGER_HOURS)
+}
+
+/// Estimates how much driving time remains before the 30-minute break is mandatory.
+///
+/// The log is walked in chronological order, accumulating driving time. Consecutive
+/// `OffDuty`, `Sleeper`, and `PersonalConveyance` segments totalling at least 30 minutes,
+/// with no unlogged time between them, form a qualifying break and reset the counter.
+///
+/// # Parameters
+/// - `segments`: A slice of `Segment` structs representing the driver's log.
+///
+/// # Returns
+/// - `Some(f32)`: The driving hours left before the 8-hour trigger (`0.0` once reached).
+/// - `None`: If the log ends with a qualifying break and no driving since.
+pub fn drive_time_until_break(segments: &[Segment]) -> Option<f32> {
+    let mut driving = 0.0;
+    let mut rest = 0.0;
+    let mut reset = false;
+    let mut previous_end = None;
+
+    for segment in sorted_by_start(segments) {
+        if is_unlogged_gap(previous_end, segment) {
+            rest = 0.0;
+        }
+        previous_end = Some(segment.end_hour);
+        let duration = segment.end_hour - segment.start_hour;
+        if segment.status == DutyStatus::Driving {
+            driving += duration;
+            rest = 0.0;
+            reset = false;
+        } else if is_break_status(&segment.status) {
+            rest += duration;
+            if rest >= BREAK_HOURS {
+                driving = 0.0;
+                reset = true;
+            }
+        } else {
+            rest = 0.0;
+        }
+    }
+
+    if reset {
+        None
+    } else {
+        Some((BREAK_TRIGGER_HOURS - driving).max(0.0))
+    }
+}
+
+/// Checks that the log includes a long enough rest, such as the 10 consecutive hours
+/// off duty required before driving.
+///
+/// A rest block is a run of consecutive off-duty, sleeper berth, and personal
+/// conveyance segments; unlogged time between segments ends the block.
+///
+/// # Parameters
+/// - `segments`: A slice of `Segment` structs representing the driver's log.
+/// - `required_hours`: The minimum length of the rest, in hours.
+///
+/// # Returns
+/// - `Ok(())`: If the longest rest block lasts at least `required_hours`.
+/// - `Err(HosViolation)`: A `HosRule::InsufficientRest` violation carrying the longest
+///   rest found, at the start hour and first segment of that block (hour `0.0` and
+///   segment `0` when the log has no rest at all).
+pub fn check_rest_adequacy(segments: &[Segment], required_hours: f32) -> Result<(), HosViolation> {
+    let mut longest = (0.0_f32, 0.0_f32, 0_usize);
+    let mut current: Option<(f32, f32, usize)> = None;
+    let mut previous_end = None;
+
+    for (index, segment) in indexed_by_start(segments) {
+        if !is_break_status(&segment.status) || is_unlogged_gap(previous_end, segment) {
+            current = None;
+        }
+        previous_end = Some(segment.end_hour);
+        if !is_break_status(&segment.status) {
+            continue;
+        }
+        let duration = segment.end_hour - segment.start_hour;
+        let block = match current {
+            Some((hours, start, first)) => (hours + duration, start, first),
+            None => (duration, segment.start_hour, index),
+        };
+        current = Some(block);
+        if block.0 > longest.0 {
+            longest = block;
+        }
+    }
+
+    let (longest_hours, hour, segment_index) = longest;
+    if longest_hours >= required_hours {
+        Ok(())
+    } else {
+        Err(HosViolation {
+            rule: HosRule::InsufficientRest { longest_hours },
+            hour,
+            segment_index,
+        })
+    }
+}
+
+/// Length, in hours, of the on-duty window opened by the first on-duty activity.
+const WINDOW_HOURS: f32 = 14.0;
+
+/// Computes the 14-hour on-duty window for the day.
+///
+/// The window opens at the first `OnDuty`, `Driving`, or `YardMove` segment and closes
+/// 14 hours later, clamped to the end of the day. Driving is not allowed past its end.
+///
+/// # Parameters
+/// - `segments`: A slice of `Segment` structs representing the driver's log.
+///
+/// # Returns
+/// - `Some((f32, f32))`: The `(start, end)` hours of the window.
+/// - `None`: If the driver never went on duty.
+pub fn on_duty_window(segments: &[Segment]) -> Option<(f32, f32)> {
+    let start = sorted_by_start(segments)
+        .into_iter()
+        .find(|segment| is_on_duty_status(&segment.status))?
+        .start_hour;
+    Some((start, (start + WINDOW_HOURS).min(24.0)))
+}
+
+/// Maximum driving hours allowed within the on-duty window.
+const DRIVING_LIMIT_HOURS: f32 = 11.0;
+
+/// Finds the hour at which cumulative driving reaches the 11-hour limit.
+///
+/// Driving segments are accumulated in chronological order; any driving after the
+/// returned hour exceeds the limit.
+///
+/// # Parameters
+/// - `segments`: A slice of `Segment` structs representing the driver's log.
+///
+/// # Returns
+/// - `Some(f32)`: The hour the limit was reached.
+/// - `None`: If the driver stayed within the limit.
+pub fn driving_limit_reached_at(segments: &[Segment]) -> Option<f32> {
+    driving_limit_crossing(segments).map(|(hour, _)| hour)
+}
+
+/// Checks a log against the Hours-of-Service rules.
+///
+/// Three rules are checked, each reported at most once:
+/// - the 11-hour driving limit, at the exact hour cumulative driving crosses it;
+/// - the 14-hour on-duty window, at the first driving after the window closed;
+/// - the 30-minute break rule (see `check_30_minute_break`).
+///
+/// The log does not need to be sorted; segment indices refer to the log as given.
+///
+/// # Parameters
+/// - `segments`: A slice of `Segment` structs representing the driver's log.
+///
+/// # Returns
+/// - `Vec<HosViolation>`: The violations found, ordered by the hour they occurred.
+pub fn check_hos(segments: &[Segment]) -> Vec<HosViolation> {
+    let mut violations = Vec::new();
+    if let Some((hour, segment_index)) = driving_limit_crossing(segments) {
+        violations.push(HosViolation {
+            rule: HosRule::DrivingLimit,
+            hour,
+            segment_index,
+        });
+    }
+    if let Some((hour, segment_index)) = window_crossing(segments) {
+        violations.push(HosViolation {
+            rule: HosRule::OnDutyWindow,
+            hour,
+            segment_index,
+        });
+    }
+    if let Err(violation) = check_30_minute_break(segments) {
+        violations.push(violation);
+    }
+    violations.sort_by(|a, b| a.hour.total_cmp(&b.hour));
+    violations
+}
+
+/// Checks whether adding a segment to the log would break an Hours-of-Service rule.
+///
+/// The proposed segment is appended to the log and the result is checked with
+/// `check_hos`. Rules the existing log already breaks are not reported again, so an
+/// editor can warn about exactly what the new segment would cause.
+///
+/// # Parameters
+/// - `existing`: The segments already in the driver's log.
+/// - `proposed`: The segment about to be added.
+///
+/// # Returns
+/// - `Vec<HosViolation>`: The violations the proposed segment would introduce; a
+///   `segment_index` of `existing.len()` refers to `proposed`.
+pub fn would_violate(existing: &[Segment], proposed: &Segment) -> Vec<HosViolation> {
+    let already_broken: Vec<HosRule> = check_hos(existing)
+        .into_iter()
+        .map(|violation| violation.rule)
+        .collect();
+
+    let mut combined = existing.to_vec();
+    combined.push(proposed.clone());
+    check_hos(&combined)
+        .into_iter()
+        .filter(|violation| !already_broken.contains(&violation.rule))
+        .collect()
+}
+
+/// Summarizes a log's compliance for a traffic-light indicator.
+///
+/// - `Compliant`: No rule is broken and every limit is more than an hour away.
+/// - `Warning`: No rule is broken yet, but the driver is within an hour of the 11-hour
+///   driving limit, of the end of the 14-hour window, or of the mandatory 30-minute break.
+/// - `Violation`: At least one rule checked by `check_hos` is broken.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Compliance {
+    Compliant,
+    Warning,
+    Violation,
+}
+
+/// Hours left before a limit at which `compliance_status` starts warning.
+const WARNING_MARGIN_HOURS: f32 = 1.0;
+
+/// Computes the compliance status of the log.
+///
+/// Violations come from `check_hos`. Otherwise the hours left before the 11-hour
+/// driving limit and the end of the 14-hour window (see `remaining_clocks`), and before
+/// the 30-minute break (see `drive_time_until_break`), decide between
+/// `Compliance::Warning` and `Compliance::Compliant`.
+///
+/// # Parameters
+/// - `segments`: A slice of `Segment` structs representing the driver's log.
+///
+/// # Returns
+/// - `Compliance`: The status to show, from `Compliant` to `Violation`.
+pub fn compliance_status(segments: &[Segment]) -> Compliance {
+    if !check_hos(segments).is_empty() {
+        return Compliance::Violation;
+    }
+    let clocks = remaining_clocks(segments, 0.0);
+    let until_break = drive_time_until_break(segments).unwrap_or(f32::INFINITY);
+    if clocks.drive.min(clocks.shift).min(until_break) <= WARNING_MARGIN_HOURS {
+        Compliance::Warning
+    } else {
+        Compliance::Compliant
+    }
+}
+
+/// Computes the latest hour the driver can keep working before a 10-hour rest.
+///
+/// Two limits are combined and the earlier one wins:
+/// - the end of the 14-hour on-duty window (see `on_duty_window`);
+/// - the hour the 11-hour driving limit would be reached if the driver kept driving
+///   from the end of the log.
+///
+/// # Parameters
+/// - `segments`: A slice of `Segment` structs representing the driver's log.
+///
+/// # Returns
+/// - `Some(f32)`: The binding cutoff hour.
+/// - `None`: If the driver has not gone on duty, so no limit is running.
+pub fn latest_work_end(segments: &[Segment]) -> Option<f32> {
+    let (_, window_end) = on_duty_window(segments)?;
+    let log_end = segments
+        .iter()
+        .map(|segment| segment.end_hour)
+        .fold(0.0, f32::max);
+    let remaining_driving = (DRIVING_LIMIT_HOURS - driving_hours(segments)).max(0.0);
+    Some(window_end.min(log_end + remaining_driving))
+}
+
+/// Largest gap, in hours, still treated as "immediately" preceding a segment (one minute).
+const CONTEXT_TOLERANCE_HOURS: f32 = 1.0 / 60.0;
+
+/// Finds driving segments that appear without an on-duty transition.
+///
+/// Driving implies being on duty, so a `Driving` segment should directly follow an
+/// `OnDuty`, `Driving`, or `YardMove` segment. Driving that starts straight out of
+/// off-duty or sleeper time, or with nothing before it, usually indicates bad data.
+/// Segments ending within a minute of the driving start count as preceding it.
+///
+/// # Parameters
+/// - `segments`: A slice of `Segment` structs representing the driver's log.
+///
+/// # Returns
+/// - `Vec<usize>`: The indices of the flagged driving segments, in log order.
+pub fn check_driving_context(segments: &[Segment]) -> Vec<usize> {
+    segments
+        .iter()
+        .enumerate()
+        .filter(|(_, segment)| segment.status == DutyStatus::Driving)
+        .filter(|(i, driving)| {
+            !segments.iter().enumerate().any(|(j, other)| {
+                j != *i
+                    && is_on_duty_status(&other.status)
+                    && (other.end_hour - driving.start_hour).abs() <= CONTEXT_TOLERANCE_HOURS
+            })
+        })
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// Maximum on-duty hours allowed in the 70-hour/8-day cycle.
+const CYCLE_LIMIT_HOURS: f32 = 70.0;
+
+/// The hours a driver has left on each of the three HOS clocks.
+///
+/// # Fields
+/// - `drive`: Driving hours left before the 11-hour limit.
+/// - `shift`: Hours left before the 14-hour on-duty window closes.
+/// - `cycle`: On-duty hours left in the 70-hour/8-day cycle.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Clocks {
+    pub drive: f32,
+    pub shift: f32,
+    pub cycle: f32,
+}
+
+impl Clocks {
+    /// Lists each clock as `(label, hours left, limit)`, in Drive/Shift/Cycle order.
+    pub fn with_limits(&self) -> [(&'static str, f32, f32); 3] {
+        [
+            ("Drive", self.drive, DRIVING_LIMIT_HOURS),
+            ("Shift", self.shift, WINDOW_HOURS),
+            ("Cycle", self.cycle, CYCLE_LIMIT_HOURS),
+        ]
+    }
+}
+
+/// Computes the hours left on the drive, shift, and cycle clocks at the end of the log.
+///
+/// The shift clock runs from the start of the on-duty window (see `on_duty_window`) and
+/// stays full while the driver has not gone on duty. The cycle clock counts today's
+/// on-duty hours on top of `cycle_hours_used`.
+///
+/// # Parameters
+/// - `segments`: A slice of `Segment` structs representing today's log.
+/// - `cycle_hours_used`: On-duty hours already used in the cycle on previous days,
+///   e.g. from `rolling_cycle_hours`.
+///
+/// # Returns
+/// - `Clocks`: The hours left on each clock, never below `0.0`.
+pub fn remaining_clocks(segments: &[Segment], cycle_hours_used: f32) -> Clocks {
+    let log_end = segments
+        .iter()
+        .map(|segment| segment.end_hour)
+        .fold(0.0, f32::max);
+    let shift = match on_duty_window(segments) {
+        Some((start, _)) => WINDOW_HOURS - (log_end - start),
+        None => WINDOW_HOURS,
+    };
+    let on_duty_today: f32 = segments
+        .iter()
+        .filter(|segment| is_on_duty_status(&segment.status))
+        .map(|segment| segment.end_hour - segment.start_hour)
+        .sum();
+
+    Clocks {
+        drive: (DRIVING_LIMIT_HOURS - driving_hours(segments)).max(0.0),
+        shift: shift.max(0.0),
+        cycle: (CYCLE_LIMIT_HOURS - cycle_hours_used - on_duty_today).max(0.0),
+    }
+}
+
+/// Finds the continuous driving shifts in the log.
+///
+/// Back-to-back `Driving` segments form one shift. When `bridge_pc` is `true`, a
+/// personal conveyance period between two driving blocks keeps the shift going, so the
+/// blocks and the PC time between them are reported as one range; PC that is not
+/// followed by more driving is left out. Any other status ends the shift.
+///
+/// # Parameters
+/// - `segments`: A slice of `Segment` structs representing the driver's log.
+/// - `bridge_pc`: Whether personal conveyance between driving blocks is bridged.
+///
+/// # Returns
+/// - `Vec<(f32, f32)>`: The `(start, end)` of each shift in chronological order.
+pub fn driving_shifts(segments: &[Segment], bridge_pc: bool) -> Vec<(f32, f32)> {
+    let mut shifts = Vec::new();
+    let mut current: Option<(f32, f32)> = None;
+    let mut bridged_to: Option<f32> = None;
+
+    for segment in sorted_by_start(segments) {
+        let continues = current.is_some_and(|(_, end)| {
+            segment.start_hour - bridged_to.unwrap_or(end) <= CONTEXT_TOLERANCE_HOURS
+        });
+        match segment.status {
+            DutyStatus::Driving => {
+                current = match current {
+                    Some((start, _)) if continues => Some((start, segment.end_hour)),
+                    _ => {
+                        shifts.extend(current);
+                        Some((segment.start_hour, segment.end_hour))
+                    }
+                };
+                bridged_to = None;
+            }
+            DutyStatus::PersonalConveyance if bridge_pc && continues => {
+                bridged_to = Some(segment.end_hour);
+            }
+            _ => {
+                shifts.extend(current.take());
+                bridged_to = None;
+            }
+        }
+    }
+    shifts.extend(current);
+    shifts
+}
+
+/// Finds pairs of rest periods that satisfy the split-sleeper provision.
+///
+/// A qualifying pair is made of one `Sleeper` segment of at least 7 hours and another
+/// `Sleeper` or `OffDuty` segment of at least 2 hours, together totalling at least
+/// 10 hours (e.g. 7 + 3 or 8 + 2). Each long sleeper period is paired with the
+/// nearest qualifying rest period in time, and a segment is used in one pair at most.
+///
+/// # Parameters
+/// - `segments`: A slice of `Segment` structs representing the driver's log.
+///
+/// # Returns
+/// - `Vec<(usize, usize)>`: The indices of each pair, earlier segment first.
+pub fn find_split_sleeper_pairs(segments: &[Segment]) -> Vec<(usize, usize)> {
+    let duration = |i: usize| segments[i].end_hour - segments[i].start_hour;
+    let is_rest = |i: usize| {
+        matches!(
+            segments[i].status,
+            DutyStatus::Sleeper | DutyStatus::OffDuty
+        )
+    };
+
+    let mut order: Vec<usize> = (0..segments.len()).collect();
+    order.sort_by(|&a, &b| segments[a].start_hour.total_cmp(&segments[b].start_hour));
+
+    let mut used = vec![false; segments.len()];
+    let mut pairs = Vec::new();
+
+    for &long in &order {
+        if used[long] || segments[long].status != DutyStatus::Sleeper || duration(long) < 7.0 {
+            continue;
+        }
+
+        let partner = order
+            .iter()
+            .copied()
+            .filter(|&short| {
+                short != long
+                    && !used[short]
+                    && is_rest(short)
+                    && duration(short) >= 2.0
+                    && duration(long) + duration(short) >= 10.0
+            })
+            .min_by(|&a, &b| {
+                let distance =
+                    |i: usize| (segments[i].start_hour - segments[long].start_hour).abs();
+                distance(a).total_cmp(&distance(b))
+            });
+
+        if let Some(short) = partner {
+            used[long] = true;
+            used[short] = true;
+            if segments[short].start_hour < segments[long].start_hour {
+                pairs.push((short, long));
+            } else {
+                pairs.push((long, short));
+            }
+        }
+    }
+
+    pairs
+}
+
+/// Describes the day's Hours-of-Service compliance in a plain sentence.
+///
+/// The sentence reports driving time against the 11-hour limit, the state of the
+/// 30-minute break, and the violations found by `check_hos` in the order they occurred,
+/// e.g. `"Driver drove 10.5 of 11 allowed hours; 30-minute break taken; no violations."`.
+/// It is meant for screen readers, for example inside an `aria-live` region next to the
+/// chart.
+///
+/// # Parameters
+/// - `segments`: A slice of `Segment` structs representing the driver's log.
+///
+/// # Returns
+/// - `String`: The description.
+pub fn describe_compliance(segments: &[Segment]) -> String {
+    let violations = check_hos(segments);
+    let break_missed = violations
+        .iter()
+        .any(|violation| violation.rule == HosRule::RestBreak);
+
+    let break_text = if break_missed {
+        "30-minute break missed"
+    } else if scan_breaks(segments, BREAK_HOURS, BREAK_TRIGGER_HOURS).taken {
+        "30-minute break taken"
+    } else {
+        "no 30-minute break required yet"
+    };
+    let violation_text = if violations.is_empty() {
+        "no violations".to_string()
+    } else {
+        let descriptions: Vec<String> = violations
+            .iter()
+            .map(|violation| match violation.rule {
+                HosRule::DrivingLimit => "11-hour driving limit exceeded".to_string(),
+                HosRule::OnDutyWindow => "driving after the 14-hour window".to_string(),
+                HosRule::RestBreak => "30-minute break missed".to_string(),
+                rule => rule.to_string(),
+            })
+            .collect();
+        format!("violations: {}", descriptions.join(", "))
+    };
+
+    format!(
+        "Driver drove {:.1} of {} allowed hours; {}; {}.",
+        driving_hours(segments),
+        DRIVING_LIMIT_HOURS,
+        break_text,
+        violation_text
+    )
+}
+
+/// Penalty for exceeding the 11-hour driving limit.
+const DRIVING_LIMIT_PENALTY: f32 = 30.0;
+
+/// Penalty for driving after the 14-hour window closed.
+const WINDOW_PENALTY: f32 = 30.0;
+
+/// Penalty for driving past 8 hours without a 30-minute break.
+const BREAK_PENALTY: f32 = 20.0;
+
+/// Penalty per hour of the day not covered by any segment.
+const GAP_PENALTY_PER_HOUR: f32 = 2.0;
+
+/// Scores the day's compliance from 0 to 100.
+///
+/// A clean, fully logged day scores `100.0`. Penalties are subtracted as follows:
+/// - 30 points when the 11-hour driving limit is exceeded;
+/// - 30 points when driving continues after the 14-hour window;
+/// - 20 points when the 30-minute break is missed;
+/// - 2 points per hour of the day left unaccounted for.
+///
+/// The score never drops below `0.0`.
+///
+/// # Parameters
+/// - `segments`: A slice of `Segment` structs representing the driver's log.
+///
+/// # Returns
+/// - `f32`: The compliance score.
+pub fn compliance_score(segments: &[Segment]) -> f32 {
+    let mut score = 100.0;
+    if driving_hours(segments) > DRIVING_LIMIT_HOURS {
+        score -= DRIVING_LIMIT_PENALTY;
+    }
+    if drove_past_window(segments) {
+        score -= WINDOW_PENALTY;
+    }
+    if check_30_minute_break(segments).is_err() {
+        score -= BREAK_PENALTY;
+    }
+    let gap_hours: f32 = find_gaps(segments)
+        .iter()
+        .map(|(start, end)| end - start)
+        .sum();
+    score -= GAP_PENALTY_PER_HOUR * gap_hours;
+    score.max(0.0)
+}
+
+/// Computes the rolling on-duty total used by the 60/70-hour cycle rules.
+///
+/// For each day, sums the hours spent On Duty, Driving, or in a Yard Move over that
+/// day and the preceding days, up to `window_days` days in total. Use a window of 7
+/// for the 60-hour/7-day rule and 8 for the 70-hour/8-day rule. Early days simply sum
+/// the days available so far.
+///
+/// # Parameters
+/// - `days`: The segments of each day, in chronological order.
+/// - `window_days`: The number of days in the rolling window.
+///
+/// # Returns
+/// - `Vec<f32>`: The rolling total for each day, in the order of `days`.
+pub fn rolling_cycle_hours(days: &[Vec<Segment>], window_days: usize) -> Vec<f32> {
+    let daily: Vec<f32> = days
+        .iter()
+        .map(|segments| {
+            segments
+                .iter()
+                .filter(|segment| is_on_duty_status(&segment.status))
+                .map(|segment| segment.end_hour - segment.start_hour)
+                .sum()
+        })
+        .collect();
+
+    (0..daily.len())
+        .map(|i| daily[(i + 1).saturating_sub(window_days)..=i].iter().sum())
+        .collect()
+}
+
+/// Counts how many times the driver switches duty status.
+///
+/// The log is sorted by `start_hour` first, and consecutive segments with the same
+/// status do not count as a switch.
+///
+/// # Parameters
+/// - `segments`: A slice of `Segment` structs representing the driver's log.
+///
+/// # Returns
+/// - `usize`: The number of status changes.
+pub fn transition_count(segments: &[Segment]) -> usize {
+    sorted_by_start(segments)
+        .windows(2)
+        .filter(|pair| pair[0].status != pair[1].status)
+        .count()
+}
+
+/// Computes the status switches per on-duty hour.
+///
+/// On-duty hours are those spent On Duty, Driving, or in a Yard Move. An unusually
+/// high rate often points at flickering data rather than real behavior.
+///
+/// # Parameters
+/// - `segments`: A slice of `Segment` structs representing the driver's log.
+///
+/// # Returns
+/// - `f32`: The transitions per on-duty hour, or `0.0` if the driver was never on duty.
+pub fn transition_rate(segments: &[Segment]) -> f32 {
+    let on_duty_hours: f32 = segments
+        .iter()
+        .filter(|segment| is_on_duty_status(&segment.status))
+        .map(|segment| segment.end_hour - segment.start_hour)
+        .sum();
+    if on_duty_hours <= 0.0 {
+        return 0.0;
+    }
+    transition_count(segments) as f32 / on_duty_hours
+}
+
+/// Returns whether unlogged time separates `segment` from the end of the previous one.
+///
+/// Rest on either side of such a gap cannot be counted as one continuous break.
+fn is_unlogged_gap(previous_end: Option<f32>, segment: &Segment) -> bool {
+    previous_end.is_some_and(|end| segment.start_hour - end > CONTEXT_TOLERANCE_HOURS)
+}
+
+/// Outcome of walking the log for a rest-break rule.
+struct BreakScan {
+    /// Whether a qualifying break was taken after some driving.
+    taken: bool,
+    /// The hour driving continued past the trigger without a break, and the index of
+    /// that driving segment, if it did.
+    missed_at: Option<(f32, usize)>,
+}
+
+/// Walks the log in order, tracking driving since the last qualifying break.
+///
+/// # Parameters
+/// - `segments`: A slice of `Segment` structs representing the driver's log.
+/// - `break_hours`: The minimum length of a qualifying break, in hours.
+/// - `trigger_hours`: The driving hours allowed before a break is required.
+fn scan_breaks(segments: &[Segment], break_hours: f32, trigger_hours: f32) -> BreakScan {
+    let mut scan = BreakScan {
+        taken: false,
+        missed_at: None,
+    };
+    let mut driving = 0.0;
+    let mut rest = 0.0;
+    let mut previous_end = None;
+
+    for (index, segment) in indexed_by_start(segments) {
+        if is_unlogged_gap(previous_end, segment) {
+            rest = 0.0;
+        }
+        previous_end = Some(segment.end_hour);
+        let duration = segment.end_hour - segment.start_hour;
+        if segment.status == DutyStatus::Driving {
+            if driving + duration > trigger_hours && scan.missed_at.is_none() {
+                let hour = segment.start_hour + (trigger_hours - driving).max(0.0);
+                scan.missed_at = Some((hour, index));
+            }
+            driving += duration;
+            rest = 0.0;
+        } else if is_break_status(&segment.status) {
+            rest += duration;
+            if rest >= break_hours && driving > 0.0 {
+                driving = 0.0;
+                scan.taken = true;
+            }
+        } else {
+            rest = 0.0;
+        }
+    }
+
+    scan
+}
+
+/// Finds where cumulative driving crosses the 11-hour limit, as `(hour, segment index)`.
+fn driving_limit_crossing(segments: &[Segment]) -> Option<(f32, usize)> {
+    let mut driven = 0.0;
+    for (index, segment) in indexed_by_start(segments) {
+        if segment.status != DutyStatus::Driving {
+            continue;
+        }
+        let duration = segment.end_hour - segment.start_hour;
+        if driven + duration > DRIVING_LIMIT_HOURS {
+            let hour = segment.start_hour + (DRIVING_LIMIT_HOURS - driven);
+            return Some((hour, index));
+        }
+        driven += duration;
+    }
+    None
+}
+
+/// Finds the first driving after the 14-hour window closed, as `(hour, segment index)`.
+fn window_crossing(segments: &[Segment]) -> Option<(f32, usize)> {
+    let (_, window_end) = on_duty_window(segments)?;
+    indexed_by_start(segments)
+        .into_iter()
+        .find(|(_, segment)| segment.status == DutyStatus::Driving && segment.end_hour > window_end)
+        .map(|(index, segment)| (segment.start_hour.max(window_end), index))
+}
+
+/// Returns whether any driving happened after the 14-hour window closed.
+fn drove_past_window(segments: &[Segment]) -> bool {
+    window_crossing(segments).is_some()
+}
+
+/// Returns whether a status opens or consumes the on-duty window.
+fn is_on_duty_status(status: &DutyStatus) -> bool {
+    matches!(
+        status,
+        DutyStatus::OnDuty | DutyStatus::Driving | DutyStatus::YardMove
+    )
+}
+
+/// Returns whether a status counts toward a rest break.
+fn is_break_status(status: &DutyStatus) -> bool {
+    matches!(
+        status,
+        DutyStatus::OffDuty | DutyStatus::Sleeper | DutyStatus::PersonalConveyance
+    )
+}
+
+/// Returns the segments ordered by `start_hour`, each with its index in the log as given.
+fn indexed_by_start(segments: &[Segment]) -> Vec<(usize, &Segment)> {
+    let mut indexed: Vec<(usize, &Segment)> = segments.iter().enumerate().collect();
+    indexed.sort_by(|a, b| a.1.start_hour.total_cmp(&b.1.start_hour));
+    indexed
+}
+
+/// Returns the segments ordered by `start_hour`, without cloning them.
+fn sorted_by_start(segments: &[Segment]) -> Vec<&Segment> {
+    let mut sorted: Vec<&Segment> = segments.iter().collect();
+    sorted.sort_by(|a, b| a.start_hour.total_cmp(&b.start_hour));
+    sorted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(start_hour: f32, end_hour: f32, status: DutyStatus) -> Segment {
+        Segment::builder(start_hour, end_hour, status).build()
+    }
+
+    /// A day with `before` hours of driving from 6:00, a break of `break_hours` in
+    /// `break_status`, then driving until `end_hour`.
+    fn day_with_break(
+        before: f32,
+        break_hours: f32,
+        break_status: DutyStatus,
+        end_hour: f32,
+    ) -> Vec<Segment> {
+        let break_start = 6.0 + before;
+        let break_end = break_start + break_hours;
+        vec![
+            segment(0.0, 6.0, DutyStatus::OffDuty),
+            segment(6.0, break_start, DutyStatus::Driving),
+            segment(break_start, break_end, break_status),
+            segment(break_end, end_hour, DutyStatus::Driving),
+            segment(end_hour, 24.0, DutyStatus::OffDuty),
+        ]
+    }
+
+    #[test]
+    fn driving_hours_excludes_personal_conveyance() {
+        let log = [
+            segment(0.0, 8.0, DutyStatus::OffDuty),
+            segment(8.0, 12.0, DutyStatus::Driving),
+            segment(12.0, 13.5, DutyStatus::PersonalConveyance),
+            segment(13.5, 24.0, DutyStatus::Sleeper),
+        ];
+        assert_eq!(driving_hours(&log), 4.0);
+        assert_eq!(off_duty_including_pc(&log), 9.5);
+    }
+
+    #[test]
+    fn break_rule_allows_exactly_eight_hours_of_driving() {
+        let log = [
+            segment(0.0, 6.0, DutyStatus::OffDuty),
+            segment(6.0, 14.0, DutyStatus::Driving),
+            segment(14.0, 24.0, DutyStatus::OffDuty),
+        ];
+        assert_eq!(check_30_minute_break(&log), Ok(()));
+    }
+
+    #[test]
+    fn break_rule_flags_driving_past_eight_hours() {
+        let log = [
+            segment(0.0, 6.0, DutyStatus::OffDuty),
+            segment(6.0, 15.0, DutyStatus::Driving),
+            segment(15.0, 24.0, DutyStatus::OffDuty),
+        ];
+        assert_eq!(
+            check_30_minute_break(&log),
+            Err(HosViolation {
+                rule: HosRule::RestBreak,
+                hour: 14.0,
+                segment_index: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn break_rule_rejects_a_29_minute_break() {
+        let log = day_with_break(4.0, 29.0 / 60.0, DutyStatus::OffDuty, 15.0);
+        let violation = check_30_minute_break(&log).unwrap_err();
+        assert_eq!(violation.rule, HosRule::RestBreak);
+        assert_eq!(violation.segment_index, 3);
+    }
+
+    #[test]
+    fn break_rule_accepts_a_30_minute_break() {
+        let log = day_with_break(4.0, 0.5, DutyStatus::OffDuty, 15.0);
+        assert_eq!(check_30_minute_break(&log), Ok(()));
+    }
+
+    #[test]
+    fn break_rule_does_not_count_on_duty_time_as_a_break() {
+        let log = day_with_break(4.0, 0.5, DutyStatus::OnDuty, 15.0);
+        assert!(check_30_minute_break(&log).is_err());
+
+        let log = day_with_break(4.0, 0.75, DutyStatus::YardMove, 15.0);
+        assert!(check_break_rule(&log, 45, 4.5).is_err());
+    }
+
+    #[test]
+    fn break_rule_supports_an_eu_style_configuration() {
+        let log = day_with_break(4.5, 0.75, DutyStatus::OffDuty, 15.0);
+        assert_eq!(check_break_rule(&log, 45, 4.5), Ok(()));
+
+        let log = day_with_break(4.5, 0.5, DutyStatus::OffDuty, 15.0);
+        assert!(check_break_rule(&log, 45, 4.5).is_err());
+    }
+
+    #[test]
+    fn drive_time_until_break_counts_down_from_eight_hours() {
+        let log = [
+            segment(0.0, 6.0, DutyStatus::OffDuty),
+            segment(6.0, 12.0, DutyStatus::Driving),
+        ];
+        assert_eq!(drive_time_until_break(&log), Some(2.0));
+    }
+
+    #[test]
+    fn drive_time_until_break_resets_after_a_break() {
+        let log = [
+            segment(0.0, 6.0, DutyStatus::OffDuty),
+            segment(6.0, 12.0, DutyStatus::Driving),
+            segment(12.0, 12.5, DutyStatus::OffDuty),
+        ];
+        assert_eq!(drive_time_until_break(&log), None);
+    }
+
+    #[test]
+    fn drive_time_until_break_is_not_reset_by_on_duty_time() {
+        let log = [
+            segment(0.0, 6.0, DutyStatus::OffDuty),
+            segment(6.0, 10.0, DutyStatus::Driving),
+            segment(10.0, 10.5, DutyStatus::OnDuty),
+            segment(10.5, 12.0, DutyStatus::Driving),
+        ];
+        assert_eq!(drive_time_until_break(&log), Some(2.5));
+    }
+
+    #[test]
+    fn drive_time_until_break_is_reset_by_personal_conveyance() {
+        let log = [
+            segment(0.0, 6.0, DutyStatus::OffDuty),
+            segment(6.0, 10.0, DutyStatus::Driving),
+            segment(10.0, 10.5, DutyStatus::PersonalConveyance),
+            segment(10.5, 12.0, DutyStatus::Driving),
+        ];
+        assert_eq!(drive_time_until_break(&log), Some(6.5));
+    }
+
+    #[test]
+    fn check_rest_adequacy_reports_the_longest_rest() {
+        let log = [
+            segment(0.0, 9.5, DutyStatus::OffDuty),
+            segment(9.5, 24.0, DutyStatus::Driving),
+        ];
+        assert_eq!(
+            check_rest_adequacy(&log, 10.0),
+            Err(HosViolation {
+                rule: HosRule::InsufficientRest { longest_hours: 9.5 },
+                hour: 0.0,
+                segment_index: 0,
+            })
+        );
+
+        let log = [
+            segment(0.0, 4.0, DutyStatus::OffDuty),
+            segment(4.0, 10.5, DutyStatus::Sleeper),
+            segment(10.5, 24.0, DutyStatus::Driving),
+        ];
+        assert_eq!(check_rest_adequacy(&log, 10.0), Ok(()));
+    }
+
+    #[test]
+    fn on_duty_window_opens_at_the_first_on_duty_activity() {
+        let log = [
+            segment(0.0, 6.0, DutyStatus::OffDuty),
+            segment(6.0, 7.0, DutyStatus::OnDuty),
+            segment(7.0, 24.0, DutyStatus::OffDuty),
+        ];
+        assert_eq!(on_duty_window(&log), Some((6.0, 20.0)));
+        assert_eq!(
+            on_duty_window(&[segment(0.0, 24.0, DutyStatus::OffDuty)]),
+            None
+        );
+    }
+
+    #[test]
+    fn latest_work_end_takes_the_binding_limit() {
+        // The window closes at 20:00, before 11 hours of driving could be used up.
+        let window_binds = [
+            segment(0.0, 6.0, DutyStatus::OffDuty),
+            segment(6.0, 16.0, DutyStatus::OnDuty),
+        ];
+        assert_eq!(latest_work_end(&window_binds), Some(20.0));
+
+        // Ten hours driven by 10:00 leave one more hour, well before the window closes.
+        let drive_binds = [segment(0.0, 10.0, DutyStatus::Driving)];
+        assert_eq!(latest_work_end(&drive_binds), Some(11.0));
+
+        assert_eq!(
+            latest_work_end(&[segment(0.0, 8.0, DutyStatus::OffDuty)]),
+            None
+        );
+    }
+
+    #[test]
+    fn find_split_sleeper_pairs_pairs_seven_and_three() {
+        let log = [
+            segment(0.0, 7.0, DutyStatus::Sleeper),
+            segment(7.0, 12.0, DutyStatus::Driving),
+            segment(12.0, 15.0, DutyStatus::OffDuty),
+            segment(15.0, 24.0, DutyStatus::OnDuty),
+        ];
+        assert_eq!(find_split_sleeper_pairs(&log), vec![(0, 2)]);
+    }
+
+    #[test]
+    fn find_split_sleeper_pairs_ignores_six_and_two() {
+        let log = [
+            segment(0.0, 6.0, DutyStatus::Sleeper),
+            segment(6.0, 12.0, DutyStatus::Driving),
+            segment(12.0, 14.0, DutyStatus::OffDuty),
+            segment(14.0, 24.0, DutyStatus::OnDuty),
+        ];
+        assert!(find_split_sleeper_pairs(&log).is_empty());
+    }
+
+    /// Twelve hours of driving from 6:00 with a 30-minute break after each four hours.
+    fn twelve_hours_driving() -> Vec<Segment> {
+        vec![
+            segment(0.0, 6.0, DutyStatus::OffDuty),
+            segment(6.0, 10.0, DutyStatus::Driving),
+            segment(10.0, 10.5, DutyStatus::OffDuty),
+            segment(10.5, 14.5, DutyStatus::Driving),
+            segment(14.5, 15.0, DutyStatus::OffDuty),
+            segment(15.0, 19.0, DutyStatus::Driving),
+            segment(19.0, 24.0, DutyStatus::OffDuty),
+        ]
+    }
+
+    #[test]
+    fn describe_compliance_reports_a_clean_day() {
+        let log = [
+            segment(0.0, 6.0, DutyStatus::OffDuty),
+            segment(6.0, 10.0, DutyStatus::Driving),
+            segment(10.0, 10.5, DutyStatus::OffDuty),
+            segment(10.5, 15.0, DutyStatus::Driving),
+            segment(15.0, 24.0, DutyStatus::OffDuty),
+        ];
+        assert_eq!(
+            describe_compliance(&log),
+            "Driver drove 8.5 of 11 allowed hours; 30-minute break taken; no violations."
+        );
+        assert_eq!(
+            describe_compliance(&[segment(0.0, 24.0, DutyStatus::OffDuty)]),
+            "Driver drove 0.0 of 11 allowed hours; no 30-minute break required yet; \
+             no violations."
+        );
+    }
+
+    #[test]
+    fn describe_compliance_lists_the_violations_from_check_hos() {
+        assert_eq!(
+            describe_compliance(&twelve_hours_driving()),
+            "Driver drove 12.0 of 11 allowed hours; 30-minute break taken; \
+             violations: 11-hour driving limit exceeded."
+        );
+
+        let log = [
+            segment(0.0, 6.0, DutyStatus::OffDuty),
+            segment(6.0, 10.0, DutyStatus::OnDuty),
+            segment(10.0, 21.0, DutyStatus::Driving),
+            segment(21.0, 24.0, DutyStatus::OffDuty),
+        ];
+        assert_eq!(check_hos(&log).len(), 2);
+        assert_eq!(
+            describe_compliance(&log),
+            "Driver drove 11.0 of 11 allowed hours; 30-minute break missed; \
+             violations: 30-minute break missed, driving after the 14-hour window."
+        );
+    }
+
+    #[test]
+    fn compliance_score_is_100_for_a_clean_day() {
+        let log = [
+            segment(0.0, 6.0, DutyStatus::OffDuty),
+            segment(6.0, 10.0, DutyStatus::Driving),
+            segment(10.0, 24.0, DutyStatus::OffDuty),
+        ];
+        assert_eq!(compliance_score(&log), 100.0);
+    }
+
+    #[test]
+    fn compliance_score_subtracts_the_driving_limit_penalty() {
+        let log = twelve_hours_driving();
+        assert_eq!(check_hos(&log).len(), 1);
+        assert_eq!(compliance_score(&log), 100.0 - DRIVING_LIMIT_PENALTY);
+    }
+
+    #[test]
+    fn check_driving_context_flags_driving_straight_from_off_duty() {
+        let log = [
+            segment(0.0, 6.0, DutyStatus::OffDuty),
+            segment(6.0, 10.0, DutyStatus::Driving),
+        ];
+        assert_eq!(check_driving_context(&log), vec![1]);
+
+        let log = [
+            segment(0.0, 6.0, DutyStatus::OffDuty),
+            segment(6.0, 6.5, DutyStatus::OnDuty),
+            segment(6.5, 10.0, DutyStatus::Driving),
+        ];
+        assert!(check_driving_context(&log).is_empty());
+    }
+
+    #[test]
+    fn remaining_clocks_counts_down_each_clock() {
+        let log = [
+            segment(0.0, 6.0, DutyStatus::OffDuty),
+            segment(6.0, 7.0, DutyStatus::OnDuty),
+            segment(7.0, 10.0, DutyStatus::Driving),
+        ];
+        assert_eq!(
+            remaining_clocks(&log, 50.0),
+            Clocks {
+                drive: 8.0,
+                shift: 10.0,
+                cycle: 16.0,
+            }
+        );
+    }
+
+    #[test]
+    fn rolling_cycle_hours_supports_70_over_8_days() {
+        let day = vec![
+            segment(0.0, 8.0, DutyStatus::OffDuty),
+            segment(8.0, 18.0, DutyStatus::Driving),
+        ];
+        let days = vec![day; 9];
+        assert_eq!(
+            rolling_cycle_hours(&days, 8),
+            vec![10.0, 20.0, 30.0, 40.0, 50.0, 60.0, 70.0, 80.0, 80.0]
+        );
+    }
+
+    #[test]
+    fn rolling_cycle_hours_supports_60_over_7_days() {
+        let day = vec![
+            segment(0.0, 1.0, DutyStatus::OnDuty),
+            segment(1.0, 9.0, DutyStatus::Driving),
+        ];
+        let days = vec![day; 8];
+        assert_eq!(
+            rolling_cycle_hours(&days, 7),
+            vec![9.0, 18.0, 27.0, 36.0, 45.0, 54.0, 63.0, 63.0]
+        );
+    }
+
+    #[test]
+    fn compliance_status_moves_from_compliant_to_violation() {
+        assert_eq!(
+            compliance_status(&[segment(0.0, 24.0, DutyStatus::OffDuty)]),
+            Compliance::Compliant
+        );
+
+        let mut log = twelve_hours_driving();
+        log[5].end_hour = 17.5;
+        log[6].start_hour = 17.5;
+        assert_eq!(driving_hours(&log), 10.5);
+        assert_eq!(compliance_status(&log), Compliance::Warning);
+
+        log[5].end_hour = 18.5;
+        log[6].start_hour = 18.5;
+        assert_eq!(driving_hours(&log), 11.5);
+        assert_eq!(compliance_status(&log), Compliance::Violation);
+    }
+
+    #[test]
+    fn compliance_status_warns_near_the_end_of_the_window() {
+        let mut log = vec![
+            segment(0.0, 6.0, DutyStatus::OffDuty),
+            segment(6.0, 8.0, DutyStatus::OnDuty),
+            segment(8.0, 12.0, DutyStatus::Driving),
+            segment(12.0, 17.0, DutyStatus::OnDuty),
+        ];
+        assert_eq!(compliance_status(&log), Compliance::Compliant);
+
+        // Half an hour of the window is left, with seven hours of driving to spare.
+        log[3].end_hour = 19.5;
+        assert_eq!(remaining_clocks(&log, 0.0).drive, 7.0);
+        assert_eq!(remaining_clocks(&log, 0.0).shift, 0.5);
+        assert_eq!(compliance_status(&log), Compliance::Warning);
+    }
+
+    #[test]
+    fn would_violate_reports_only_new_violations() {
+        let existing = [
+            segment(0.0, 6.0, DutyStatus::OffDuty),
+            segment(6.0, 10.0, DutyStatus::Driving),
+            segment(10.0, 10.5, DutyStatus::OffDuty),
+            segment(10.5, 14.5, DutyStatus::Driving),
+            segment(14.5, 15.0, DutyStatus::OffDuty),
+            segment(15.0, 17.0, DutyStatus::Driving),
+        ];
+        assert_eq!(
+            would_violate(&existing, &segment(17.0, 19.0, DutyStatus::Driving)),
+            vec![HosViolation {
+                rule: HosRule::DrivingLimit,
+                hour: 18.0,
+                segment_index: 6,
+            }]
+        );
+        assert!(would_violate(&existing, &segment(17.0, 17.5, DutyStatus::Driving)).is_empty());
+    }
+
+    #[test]
+    fn a_smooth_day_has_one_transition_per_status_change() {
+        let log = [
+            segment(7.0, 15.0, DutyStatus::Driving),
+            segment(0.0, 6.0, DutyStatus::OffDuty),
+            segment(6.0, 7.0, DutyStatus::OnDuty),
+            segment(15.0, 20.0, DutyStatus::Sleeper),
+            segment(20.0, 24.0, DutyStatus::Sleeper),
+        ];
+        assert_eq!(transition_count(&log), 3);
+        assert_eq!(transition_rate(&log), 3.0 / 9.0);
+    }
+
+    #[test]
+    fn a_flickery_log_has_a_high_transition_rate() {
+        let log: Vec<Segment> = (0..12)
+            .map(|i| {
+                let start = 6.0 + i as f32 * 0.5;
+                let status = if i % 2 == 0 {
+                    DutyStatus::Driving
+                } else {
+                    DutyStatus::OnDuty
+                };
+                segment(start, start + 0.5, status)
+            })
+            .collect();
+        assert_eq!(transition_count(&log), 11);
+        assert_eq!(transition_rate(&log), 11.0 / 6.0);
+    }
+
+    #[test]
+    fn transition_rate_is_zero_without_on_duty_time() {
+        let log = [
+            segment(0.0, 8.0, DutyStatus::OffDuty),
+            segment(8.0, 24.0, DutyStatus::Sleeper),
+        ];
+        assert_eq!(transition_count(&log), 1);
+        assert_eq!(transition_rate(&log), 0.0);
+    }
+
+    #[test]
+    fn driving_shifts_bridge_personal_conveyance_only_when_asked() {
+        let log = [
+            segment(0.0, 6.0, DutyStatus::OffDuty),
+            segment(6.0, 9.0, DutyStatus::Driving),
+            segment(9.0, 10.0, DutyStatus::PersonalConveyance),
+            segment(10.0, 13.0, DutyStatus::Driving),
+            segment(13.0, 14.0, DutyStatus::PersonalConveyance),
+            segment(14.0, 24.0, DutyStatus::OffDuty),
+        ];
+        assert_eq!(driving_shifts(&log, true), vec![(6.0, 13.0)]);
+        assert_eq!(driving_shifts(&log, false), vec![(6.0, 9.0), (10.0, 13.0)]);
+    }
+
+    #[test]
+    fn check_hos_reports_the_exact_hour_a_segment_crosses_the_window() {
+        let log = [
+            segment(0.0, 5.0, DutyStatus::OffDuty),
+            segment(5.0, 6.0, DutyStatus::OnDuty),
+            segment(6.0, 10.0, DutyStatus::Driving),
+            segment(10.0, 12.0, DutyStatus::OffDuty),
+            segment(12.0, 17.0, DutyStatus::OnDuty),
+            segment(17.0, 20.5, DutyStatus::Driving),
+            segment(20.5, 24.0, DutyStatus::OffDuty),
+        ];
+        assert_eq!(
+            check_hos(&log),
+            vec![HosViolation {
+                rule: HosRule::OnDutyWindow,
+                hour: 19.0,
+                segment_index: 5,
+            }]
+        );
+    }
+
+    #[test]
+    fn check_hos_gives_the_same_result_for_unsorted_input() {
+        let sorted = [
+            segment(0.0, 5.0, DutyStatus::OffDuty),
+            segment(5.0, 14.0, DutyStatus::Driving),
+            segment(14.0, 14.5, DutyStatus::OffDuty),
+            segment(14.5, 21.0, DutyStatus::Driving),
+            segment(21.0, 24.0, DutyStatus::OffDuty),
+        ];
+        let mut unsorted = sorted.to_vec();
+        unsorted.reverse();
+
+        let expected = check_hos(&sorted);
+        assert_eq!(expected.len(), 3);
+        let reported = check_hos(&unsorted);
+        assert_eq!(reported.len(), expected.len());
+        for (violation, expected) in reported.iter().zip(&expected) {
+            assert_eq!(violation.rule, expected.rule);
+            assert_eq!(violation.hour, expected.hour);
+            assert_eq!(
+                unsorted[violation.segment_index],
+                sorted[expected.segment_index]
+            );
+        }
+    }
+}
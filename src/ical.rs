@@ -0,0 +1,214 @@
+//! Exports a driver's log as iCalendar (RFC 5545) events.
+//!
+//! Each duty period other than off-duty time becomes a `VEVENT`, so drivers can see
+//! their working time in a calendar app. Times are written as floating local times (no time
+//! zone), matching how the log itself records hours of the day.
+
+use crate::chart::{status_label, DutyStatus, Segment};
+use std::fmt::Write;
+
+/// Product identifier written in the calendar header.
+const PRODUCT_ID: &str = "-//opensass//eld//EN";
+
+/// Converts the log into an iCalendar document with one event per duty period.
+///
+/// Off-duty and personal conveyance segments are skipped, since both count as off-duty
+/// time. Each event has the status as its `SUMMARY`, the location as its `LOCATION`,
+/// and the note, if any, as its `DESCRIPTION`. Hours past midnight roll over into the
+/// following days. Every event is stamped (`DTSTAMP`) with the time of the export.
+///
+/// # Parameters
+/// - `segments`: A slice of `Segment` structs representing the driver's log.
+/// - `date`: The day of the log, as `YYYY-MM-DD`.
+///
+/// # Returns
+/// - `String`: The `VCALENDAR` document, with CRLF line endings. It has no events if
+///   `date` is not a valid `YYYY-MM-DD` date.
+pub fn to_ics(segments: &[Segment], date: &str) -> String {
+    let mut ics = String::new();
+    ics.push_str("BEGIN:VCALENDAR\r\n");
+    ics.push_str("VERSION:2.0\r\n");
+    let _ = write!(ics, "PRODID:{PRODUCT_ID}\r\n");
+
+    let Some(day) = parse_date(date) else {
+        log::warn!("Invalid log date '{}', expected YYYY-MM-DD", date);
+        ics.push_str("END:VCALENDAR\r\n");
+        return ics;
+    };
+
+    let stamp = format_utc_stamp(unix_seconds_now());
+    for (index, segment) in segments.iter().enumerate() {
+        if matches!(
+            segment.status,
+            DutyStatus::OffDuty | DutyStatus::PersonalConveyance
+        ) {
+            continue;
+        }
+        ics.push_str("BEGIN:VEVENT\r\n");
+        let _ = write!(ics, "UID:{}-{}@eld\r\n", format_date(day), index);
+        let _ = write!(ics, "DTSTAMP:{stamp}\r\n");
+        let _ = write!(
+            ics,
+            "DTSTART:{}\r\n",
+            format_date_time(day, segment.start_hour)
+        );
+        let _ = write!(ics, "DTEND:{}\r\n", format_date_time(day, segment.end_hour));
+        let _ = write!(ics, "SUMMARY:{}\r\n", status_label(&segment.status));
+        if !segment.location.is_empty() {
+            let _ = write!(ics, "LOCATION:{}\r\n", escape_text(&segment.location));
+        }
+        if !segment.note.is_empty() {
+            let _ = write!(ics, "DESCRIPTION:{}\r\n", escape_text(&segment.note));
+        }
+        ics.push_str("END:VEVENT\r\n");
+    }
+
+    ics.push_str("END:VCALENDAR\r\n");
+    ics
+}
+
+/// Parses a `YYYY-MM-DD` date into its year, month, and day.
+fn parse_date(date: &str) -> Option<(u32, u32, u32)> {
+    let mut parts = date.split('-').map(|part| part.parse::<u32>().ok());
+    let (year, month, day) = (parts.next()??, parts.next()??, parts.next()??);
+    if parts.next().is_some() || !(1..=12).contains(&month) {
+        return None;
+    }
+    (1..=days_in_month(year, month))
+        .contains(&day)
+        .then_some((year, month, day))
+}
+
+/// Returns the number of days in a month of the Gregorian calendar.
+fn days_in_month(year: u32, month: u32) -> u32 {
+    match month {
+        4 | 6 | 9 | 11 => 30,
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        _ => 31,
+    }
+}
+
+/// Moves a date forward by a number of days.
+fn add_days((mut year, mut month, mut day): (u32, u32, u32), days: u32) -> (u32, u32, u32) {
+    for _ in 0..days {
+        day += 1;
+        if day > days_in_month(year, month) {
+            day = 1;
+            month += 1;
+            if month > 12 {
+                month = 1;
+                year += 1;
+            }
+        }
+    }
+    (year, month, day)
+}
+
+/// Formats a date as `YYYYMMDD`.
+fn format_date((year, month, day): (u32, u32, u32)) -> String {
+    format!("{year:04}{month:02}{day:02}")
+}
+
+/// Formats an hour of the log's day as a floating `YYYYMMDDTHHMMSS` date-time.
+fn format_date_time(date: (u32, u32, u32), hour: f32) -> String {
+    let seconds = (hour.max(0.0) * 3600.0).round() as u32;
+    let day_seconds = seconds % 86_400;
+    format!(
+        "{}T{:02}{:02}{:02}",
+        format_date(add_days(date, seconds / 86_400)),
+        day_seconds / 3600,
+        day_seconds / 60 % 60,
+        day_seconds % 60
+    )
+}
+
+/// Returns the current time as seconds since the Unix epoch.
+#[cfg(target_arch = "wasm32")]
+fn unix_seconds_now() -> u64 {
+    (js_sys::Date::now() / 1000.0) as u64
+}
+
+/// Returns the current time as seconds since the Unix epoch.
+#[cfg(not(target_arch = "wasm32"))]
+fn unix_seconds_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+/// Formats seconds since the Unix epoch as a UTC `YYYYMMDDTHHMMSSZ` date-time.
+fn format_utc_stamp(unix_seconds: u64) -> String {
+    let days = (unix_seconds / 86_400) as u32;
+    let hour = (unix_seconds % 86_400) as f32 / 3600.0;
+    format!("{}Z", format_date_time(add_days((1970, 1, 1), days), hour))
+}
+
+/// Escapes text for an iCalendar `TEXT` value.
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exports_a_driving_segment_as_an_event() {
+        let log = [
+            Segment::builder(0.0, 6.0, DutyStatus::OffDuty).build(),
+            Segment::builder(6.0, 10.5, DutyStatus::Driving)
+                .location("Dallas, TX")
+                .note("Loaded; heading north")
+                .build(),
+        ];
+        let ics = to_ics(&log, "2025-03-14");
+
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 1);
+        assert!(ics.contains("UID:20250314-1@eld\r\n"));
+        assert!(ics.contains("DTSTART:20250314T060000\r\n"));
+        assert!(ics.contains("DTEND:20250314T103000\r\n"));
+        assert!(ics.contains("SUMMARY:Driving\r\n"));
+        assert!(ics.contains("LOCATION:Dallas\\, TX\r\n"));
+        assert!(ics.contains("DESCRIPTION:Loaded\\; heading north\r\n"));
+    }
+
+    #[test]
+    fn rolls_hours_past_midnight_into_the_next_day() {
+        let log = [Segment::builder(22.0, 25.5, DutyStatus::Driving).build()];
+        let ics = to_ics(&log, "2024-12-31");
+
+        assert!(ics.contains("DTSTART:20241231T220000\r\n"));
+        assert!(ics.contains("DTEND:20250101T013000\r\n"));
+    }
+
+    #[test]
+    fn skips_off_duty_and_personal_conveyance() {
+        let log = [
+            Segment::builder(0.0, 6.0, DutyStatus::OffDuty).build(),
+            Segment::builder(6.0, 7.0, DutyStatus::PersonalConveyance).build(),
+            Segment::builder(7.0, 8.0, DutyStatus::YardMove).build(),
+        ];
+        let ics = to_ics(&log, "2025-03-14");
+
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 1);
+        assert!(ics.contains("SUMMARY:Yard Move\r\n"));
+    }
+
+    #[test]
+    fn writes_no_events_for_an_invalid_date() {
+        let log = [Segment::builder(6.0, 10.0, DutyStatus::Driving).build()];
+        assert!(!to_ics(&log, "2025-02-30").contains("BEGIN:VEVENT"));
+    }
+
+    #[test]
+    fn formats_the_stamp_in_utc() {
+        assert_eq!(format_utc_stamp(0), "19700101T000000Z");
+        assert_eq!(format_utc_stamp(951_827_696), "20000229T123456Z");
+    }
+}
@@ -1 +1,563 @@
+#![doc = include_str!("../LEPTOS.md")]
 
+use crate::chart::effective_width;
+use crate::chart::forget_grid;
+use crate::chart::redraw_base;
+use crate::chart::segment_at;
+use crate::chart::segment_tooltip;
+use crate::chart::Annotation;
+use crate::chart::ChartProps as DrawProps;
+use crate::chart::ChartStyle;
+use crate::chart::DutyStatus;
+use crate::chart::GridMode;
+use crate::chart::Segment;
+use crate::chart::SegmentStyle;
+use crate::chart::{Align, OverflowMode};
+use crate::draw_chart;
+use crate::redraw_chart;
+use leptos::prelude::*;
+use std::borrow::Cow;
+
+/// Chart Component
+///
+/// A Leptos component that renders a **duty status chart** inside a `<canvas>` element.
+/// This chart visually represents different duty statuses (Off Duty, Sleeper, Driving, On Duty)
+/// based on the provided data. It accepts the same properties as the Dioxus `Chart`.
+///
+/// # Properties
+/// - **data** *(Signal<Vec<Segment>>)* - The log data; accepts a `ReadSignal`, `RwSignal`, or `Signal`.
+/// - **width** *(u32)* - The width of the chart in pixels.
+/// - **height** *(u32)* - The height of the chart in pixels.
+/// - **background_color** *(String)* - The background color of the chart.
+/// - **grid_color** *(String)* - The color of the grid lines.
+/// - **font** *(String)* - The font used for labels.
+/// - **label_color** *(String)* - The color of the labels.
+/// - **off_duty_color** *(String)* - The color representing **Off Duty** time.
+/// - **sleeper_color** *(String)* - The color representing **Sleeper Berth** time.
+/// - **driving_color** *(String)* - The color representing **Driving** time.
+/// - **on_duty_color** *(String)* - The color representing **On Duty** time.
+/// - **personal_conveyance_color** *(String)* - The color representing **Personal Conveyance** time.
+/// - **yard_move_color** *(String)* - The color representing **Yard Move** time.
+/// - **grid_mode** *(GridMode)* - Which vertical grid lines are drawn.
+/// - **flip_y** *(bool)* - Whether the status rows are drawn bottom-up.
+/// - **annotations** *(Vec<Annotation>)* - Labeled markers drawn at specific points.
+/// - **footer** *(Option<String>)* - Text drawn at the bottom-right corner of the canvas.
+/// - **corner_radius** *(f64)* - The radius of the chart's rounded corners.
+/// - **style** *(ChartStyle)* - Whether segments are drawn as row lines or a ribbon.
+/// - **integer_layout** *(bool)* - Whether coordinates are rounded to whole pixels.
+/// - **split_sleeper_brackets** *(bool)* - Whether split-sleeper pairs are bracketed.
+/// - **visible_range** *(Option<(f64, f64)>)* - The visible pixel range; other segments are skipped.
+/// - **hours_span** *(f32)* - The number of hours covered by the time axis.
+/// - **on_overflow** *(OverflowMode)* - How segments past `hours_span` are drawn.
+/// - **row_heights** *(Option<[f64; 4]>)* - Relative heights of the four status rows.
+/// - **background_gradient** *(Option<(String, String)>)* - A top-to-bottom background gradient.
+/// - **night_window** *(Option<(f32, f32)>)* - The night hours tinted behind the segments.
+/// - **night_tint_color** *(String)* - The color of the night tint.
+/// - **side_panel** *(bool)* - Whether a swatch, label, and total per status is drawn on the right.
+/// - **segment_style** *(SegmentStyle)* - Whether segments are drawn as lines or rounded pills.
+/// - **pill_height** *(f64)* - The height of each pill in `SegmentStyle::Pill` mode.
+/// - **highlight_overage** *(bool)* - Whether driving past the 11-hour limit is highlighted.
+/// - **overage_color** *(String)* - The color of driving past the 11-hour limit.
+/// - **min_segment_px** *(f64)* - The minimum drawn width of each segment.
+/// - **compact_labels** *(bool)* - Whether the row labels are abbreviated.
+/// - **canvas_id** *(String)* - The `id` of the `<canvas>` element.
+/// - **legend_active_only** *(bool)* - Whether the legend only lists statuses present in the data.
+/// - **min_width** *(Option<u32>)* - The smallest width of a responsive chart.
+/// - **max_width** *(Option<u32>)* - The largest width of a responsive chart.
+/// - **end_of_day_status** *(Option<DutyStatus>)* - The status filled in after the last segment until hour 24.
+/// - **segment_border** *(Option<(String, f64)>)* - A border around each pill or ribbon segment.
+/// - **start_hour** *(f32)* - The first hour shown on the time axis.
+/// - **end_hour** *(f32)* - The last hour shown on the time axis.
+/// - **show_odometer** *(bool)* - Whether odometer readings are drawn at status changes.
+/// - **align** *(Align)* - Where the chart is placed on a canvas wider than `max_width`.
+/// - **now_hour** *(Option<f32>)* - The current hour, marked with a "NOW" line.
+/// - **now_color** *(String)* - The color of the `now_hour` marker.
+/// - **container_style** *(String)* - Inline style of the container `div`.
+/// - **container_class** *(String)* - CSS class of the container `div`.
+/// - **canvas_style** *(String)* - Inline style of the `<canvas>` element.
+/// - **canvas_class** *(String)* - CSS class of the `<canvas>` element.
+/// - **interactive** *(bool)* - Whether the chart reacts to user input.
+/// - **on_scroll** *(Option<Callback<f64>>)* - Called with the container's scroll offset in pixels.
+/// - **on_segment_click** *(Option<Callback<usize>>)* - Called with the index of the clicked segment.
+/// - **on_redraw** *(Option<Callback<u32>>)* - Called with the cumulative redraw count (debug builds only).
+///
+/// # Examples
+///
+/// ## Basic Usage
+/// This example renders a `Chart` component with sample data:
+///
+/// ```rust
+/// use leptos::prelude::*;
+/// use eld::chart::sample_day;
+/// use eld::leptos::Chart;
+///
+/// #[component]
+/// fn App() -> impl IntoView {
+///     let (eld_data, _set_eld_data) = signal(sample_day());
+///
+///     view! {
+///         <Chart
+///             data=eld_data
+///             width=800
+///             height=400
+///             background_color="#ffffff"
+///             grid_color="#cccccc"
+///             font="12px Arial"
+///             label_color="#333333"
+///             off_duty_color="#dddddd"
+///             sleeper_color="#000000"
+///             driving_color="#28a745"
+///             on_duty_color="#ff9800"
+///         />
+///     }
+/// }
+/// ```
+///
+/// # Behavior
+/// - When `data` changes, an `Effect` **redraws the chart**.
+/// - The grid is only redrawn when the canvas size, pixel ratio, or grid settings change, and is
+///   forgotten when the component is cleaned up so a remounted chart draws it again.
+/// - The chart is **scrollable horizontally** for better visibility on smaller screens,
+///   unless `container_style` overrides the default container style.
+/// - When `interactive`, hovering a segment shows a tooltip with its status, time range,
+///   location, and note (see `segment_tooltip`), hidden again when the cursor leaves.
+/// - Uses the `draw_chart` function to render the chart inside the `<canvas>` element.
+///
+/// # Notes
+/// - The `<canvas>` must have a unique `id` (`canvas_id`, `eld-canvas` by default) for proper rendering.
+#[component]
+pub fn Chart(
+    /// The dataset for the chart.
+    ///
+    /// A signal that holds a vector of `Segment` values. The chart redraws whenever it changes.
+    #[prop(into)]
+    data: Signal<Vec<Segment>>,
+
+    /// The width of the canvas in pixels. Defaults to `900`.
+    #[prop(default = 900)]
+    width: u32,
+
+    /// The height of the canvas in pixels. Defaults to `300`.
+    #[prop(default = 300)]
+    height: u32,
+
+    /// The background color of the chart. Defaults to `"#FFFFFF"` (white).
+    #[prop(into, default = Cow::Borrowed("#FFFFFF"))]
+    background_color: Cow<'static, str>,
+
+    /// The color of the grid lines. Defaults to `"#CCCCCC"` (light gray).
+    #[prop(into, default = Cow::Borrowed("#CCCCCC"))]
+    grid_color: Cow<'static, str>,
+
+    /// The font used for labels, in CSS font syntax. Defaults to `"bold 14px Arial"`.
+    #[prop(default = "bold 14px Arial")]
+    font: &'static str,
+
+    /// The color of the axis labels. Defaults to `"#444444"` (dark gray).
+    #[prop(into, default = Cow::Borrowed("#444444"))]
+    label_color: Cow<'static, str>,
+
+    /// The color used to represent "Off Duty" status. Defaults to `"#8E8E8E"` (gray).
+    #[prop(into, default = Cow::Borrowed("#8E8E8E"))]
+    off_duty_color: Cow<'static, str>,
+
+    /// The color used to represent "Sleeper" status. Defaults to `black`.
+    #[prop(into, default = Cow::Borrowed("black"))]
+    sleeper_color: Cow<'static, str>,
+
+    /// The color used to represent "Driving" status. Defaults to `green`.
+    #[prop(into, default = Cow::Borrowed("green"))]
+    driving_color: Cow<'static, str>,
+
+    /// The color used to represent "On Duty" status. Defaults to `orange`.
+    #[prop(into, default = Cow::Borrowed("orange"))]
+    on_duty_color: Cow<'static, str>,
+
+    /// The color used to represent "Personal Conveyance" status. Defaults to `steelblue`.
+    #[prop(into, default = Cow::Borrowed("steelblue"))]
+    personal_conveyance_color: Cow<'static, str>,
+
+    /// The color used to represent "Yard Move" status. Defaults to `goldenrod`.
+    #[prop(into, default = Cow::Borrowed("goldenrod"))]
+    yard_move_color: Cow<'static, str>,
+
+    /// Which vertical grid lines are drawn. Defaults to `GridMode::Hourly`.
+    #[prop(optional)]
+    grid_mode: GridMode,
+
+    /// Whether the status rows are drawn in reverse order. Defaults to `false`.
+    #[prop(optional)]
+    flip_y: bool,
+
+    /// Labeled markers drawn at specific `(hour, status)` points. Defaults to none.
+    #[prop(optional)]
+    annotations: Vec<Annotation>,
+
+    /// Text drawn at the bottom-right corner of the canvas. Defaults to no footer.
+    #[prop(optional, into)]
+    footer: Option<String>,
+
+    /// The radius, in pixels, of the chart's rounded corners. Defaults to `0.0`.
+    #[prop(optional)]
+    corner_radius: f64,
+
+    /// How the duty status segments are visualized. Defaults to `ChartStyle::GridLines`.
+    #[prop(optional)]
+    style: ChartStyle,
+
+    /// Whether all coordinates are rounded to whole pixels. Defaults to `false`.
+    #[prop(optional)]
+    integer_layout: bool,
+
+    /// Whether split-sleeper rest pairs are linked with a bracket. Defaults to `false`.
+    #[prop(optional)]
+    split_sleeper_brackets: bool,
+
+    /// The horizontal pixel range that is visible; other segments are skipped.
+    /// Defaults to drawing every segment.
+    #[prop(optional)]
+    visible_range: Option<(f64, f64)>,
+
+    /// The number of hours covered by the time axis. Defaults to `24.0`.
+    #[prop(default = 24.0)]
+    hours_span: f32,
+
+    /// How segments running past `hours_span` are drawn. Defaults to `OverflowMode::Clip`.
+    #[prop(optional)]
+    on_overflow: OverflowMode,
+
+    /// Relative heights of the Off Duty, Sleeper, Driving, and On Duty rows.
+    /// Defaults to equal heights.
+    #[prop(optional)]
+    row_heights: Option<[f64; 4]>,
+
+    /// A vertical background gradient, as `(top_color, bottom_color)`. Defaults to none.
+    #[prop(optional)]
+    background_gradient: Option<(String, String)>,
+
+    /// The night hours, as `(start, end)`, tinted behind the segments. Defaults to none.
+    #[prop(optional)]
+    night_window: Option<(f32, f32)>,
+
+    /// The translucent color used to tint the night hours.
+    /// Defaults to `"rgba(25, 25, 112, 0.1)"`.
+    #[prop(into, default = Cow::Borrowed("rgba(25, 25, 112, 0.1)"))]
+    night_tint_color: Cow<'static, str>,
+
+    /// Whether a summary panel is drawn to the right of the grid. Defaults to `false`.
+    #[prop(optional)]
+    side_panel: bool,
+
+    /// How each segment is drawn within its status row. Defaults to `SegmentStyle::Line`.
+    #[prop(optional)]
+    segment_style: SegmentStyle,
+
+    /// The height, in pixels, of each bar in `SegmentStyle::Pill` mode. Defaults to `12.0`.
+    #[prop(default = 12.0)]
+    pill_height: f64,
+
+    /// Whether driving beyond the 11-hour limit is drawn in `overage_color`.
+    /// Defaults to `false`.
+    #[prop(optional)]
+    highlight_overage: bool,
+
+    /// The color of driving time past the 11-hour limit. Defaults to `"red"`.
+    #[prop(into, default = Cow::Borrowed("red"))]
+    overage_color: Cow<'static, str>,
+
+    /// The minimum drawn width, in pixels, of each segment. Defaults to `0.0`.
+    #[prop(optional)]
+    min_segment_px: f64,
+
+    /// Whether the row labels are abbreviated. Defaults to `false`.
+    #[prop(optional)]
+    compact_labels: bool,
+
+    /// The `id` of the `<canvas>` element; the container gets `"{canvas_id}-container"`.
+    /// Defaults to `"eld-canvas"`.
+    #[prop(default = "eld-canvas")]
+    canvas_id: &'static str,
+
+    /// Whether the side panel legend only lists statuses present in the data.
+    /// Defaults to `false`.
+    #[prop(optional)]
+    legend_active_only: bool,
+
+    /// The smallest width, in pixels, of a chart that fills its container. Defaults to none.
+    #[prop(optional)]
+    min_width: Option<u32>,
+
+    /// The largest width, in pixels, of a chart that fills its container. Defaults to none.
+    #[prop(optional)]
+    max_width: Option<u32>,
+
+    /// The status assumed after the last segment until the end of the day.
+    /// Defaults to `Some(DutyStatus::OffDuty)`; `None` leaves the rest of the day blank.
+    #[prop(default = Some(DutyStatus::OffDuty))]
+    end_of_day_status: Option<DutyStatus>,
+
+    /// A border stroked around each pill or ribbon segment, as `(color, line_width)`.
+    /// Defaults to no border.
+    #[prop(optional)]
+    segment_border: Option<(String, f64)>,
+
+    /// The first hour shown on the time axis. Defaults to `0.0`.
+    #[prop(optional)]
+    start_hour: f32,
+
+    /// The last hour shown on the time axis. Defaults to `24.0` (the end of `hours_span`).
+    #[prop(default = 24.0)]
+    end_hour: f32,
+
+    /// Whether odometer readings are drawn below the grid. Defaults to `false`.
+    #[prop(optional)]
+    show_odometer: bool,
+
+    /// Where the chart is placed on a canvas wider than `max_width`. Defaults to `Align::Left`.
+    #[prop(optional)]
+    align: Align,
+
+    /// The current hour of the day, marked with a vertical "NOW" line. Defaults to none.
+    #[prop(optional)]
+    now_hour: Option<f32>,
+
+    /// The color of the `now_hour` marker. Defaults to `"#E53935"`.
+    #[prop(into, default = Cow::Borrowed("#E53935"))]
+    now_color: Cow<'static, str>,
+
+    /// Inline style applied to the container `div` wrapping the canvas.
+    /// Defaults to `"position: relative; max-width: 100%; overflow-x: auto;"`.
+    #[prop(default = "position: relative; max-width: 100%; overflow-x: auto;")]
+    container_style: &'static str,
+
+    /// CSS class applied to the container `div`. Defaults to no class.
+    #[prop(optional)]
+    container_class: &'static str,
+
+    /// Inline style applied to the `<canvas>` element; the background color and cursor
+    /// are always appended. Defaults to `"border: 1px solid black;"`.
+    #[prop(default = "border: 1px solid black;")]
+    canvas_style: &'static str,
+
+    /// CSS class applied to the `<canvas>` element. Defaults to no class.
+    #[prop(optional)]
+    canvas_class: &'static str,
+
+    /// Whether the chart reacts to user input. Defaults to `true`.
+    #[prop(default = true)]
+    interactive: bool,
+
+    /// Called with the container's `scrollLeft`, in pixels, as the user scrolls.
+    #[prop(optional, into)]
+    on_scroll: Option<Callback<f64>>,
+
+    /// Called with the index, in `data`, of the segment the user clicks on. Only fires
+    /// when `interactive` is `true`.
+    #[prop(optional, into)]
+    on_segment_click: Option<Callback<usize>>,
+
+    /// Called with the cumulative number of redraws; only fires in debug builds.
+    #[prop(optional, into)]
+    on_redraw: Option<Callback<u32>>,
+) -> impl IntoView {
+    on_cleanup(move || forget_grid(canvas_id));
+
+    let cursor = if interactive { "pointer" } else { "default" };
+    let canvas_style =
+        format!("{canvas_style} cursor: {cursor}; background-color: {background_color};");
+
+    let mut redraws = 0_u32;
+    let drawn_props = StoredValue::new(None::<DrawProps>);
+    let drawn_segments = StoredValue::new(None::<Vec<Segment>>);
+    let (tooltip, set_tooltip) = signal(None::<(String, f64, f64)>);
+    Effect::new(move |_| {
+        let chart_props = DrawProps {
+            width,
+            height,
+            background_color: background_color.to_string(),
+            grid_color: grid_color.to_string(),
+            font: font.to_string(),
+            label_color: label_color.to_string(),
+            off_duty_color: off_duty_color.to_string(),
+            sleeper_color: sleeper_color.to_string(),
+            driving_color: driving_color.to_string(),
+            on_duty_color: on_duty_color.to_string(),
+            personal_conveyance_color: personal_conveyance_color.to_string(),
+            yard_move_color: yard_move_color.to_string(),
+            grid_mode,
+            flip_y,
+            annotations: annotations.clone(),
+            footer: footer.clone(),
+            corner_radius,
+            style,
+            integer_layout,
+            split_sleeper_brackets,
+            visible_range,
+            hours_span,
+            on_overflow,
+            row_heights,
+            background_gradient: background_gradient.clone(),
+            night_window,
+            night_tint_color: night_tint_color.to_string(),
+            side_panel,
+            segment_style,
+            pill_height,
+            highlight_overage,
+            overage_color: overage_color.to_string(),
+            min_segment_px,
+            compact_labels,
+            canvas_id: canvas_id.to_string(),
+            legend_active_only,
+            min_width,
+            max_width,
+            end_of_day_status: end_of_day_status.clone(),
+            segment_border: segment_border.clone(),
+            start_hour,
+            end_hour,
+            show_odometer,
+            align,
+            now_hour,
+            now_color: now_color.to_string(),
+            // Only used by `draw_team`, which the component does not draw.
+            driver_b_dash: Vec::new(),
+        };
+        let width = container_width(canvas_id).map_or(chart_props.width, |available| {
+            effective_width(available, &chart_props)
+        });
+        let chart_props = DrawProps {
+            width,
+            ..chart_props
+        };
+
+        let segments = data.get();
+        // Only the changed segments need repainting while the props stay the same.
+        let result = drawn_props.with_value(|drawn| {
+            drawn_segments.with_value(|previous| {
+                match redraw_base(previous.as_deref(), drawn.as_ref(), &chart_props) {
+                    Some(previous) => redraw_chart(previous, &segments, &chart_props),
+                    None => draw_chart(&segments, &chart_props),
+                }
+            })
+        });
+        if let Err(err) = result {
+            log::error!("Failed to draw chart: {}", err);
+        }
+        drawn_props.set_value(Some(chart_props));
+        drawn_segments.set_value(Some(segments));
+
+        if let Some(on_redraw) = on_redraw.filter(|_| cfg!(debug_assertions)) {
+            redraws += 1;
+            on_redraw.run(redraws);
+        }
+    });
+
+    let chart = view! {
+        {if interactive {
+            view! {
+                <canvas
+                    id=canvas_id
+                    class=canvas_class
+                    style=canvas_style
+                    on:click=move |event| {
+                        let Some(on_segment_click) = on_segment_click else {
+                            return;
+                        };
+                        let Some(chart_props) = drawn_props.get_value() else {
+                            return;
+                        };
+                        let (x, y) = (event.offset_x() as f64, event.offset_y() as f64);
+                        if let Some(index) = data
+                            .with_untracked(|segments| segment_at(segments, &chart_props, x, y))
+                        {
+                            on_segment_click.run(index);
+                        }
+                    }
+                    on:mousemove=move |event| {
+                        let (x, y) = (event.offset_x() as f64, event.offset_y() as f64);
+                        let hovered = drawn_props.with_value(|chart_props| {
+                            let chart_props = chart_props.as_ref()?;
+                            data.with_untracked(|segments| {
+                                segment_at(segments, chart_props, x, y)
+                                    .map(|index| segment_tooltip(&segments[index]))
+                            })
+                        });
+                        set_tooltip.set(
+                            hovered.map(|text| (text, x + TOOLTIP_OFFSET, y + TOOLTIP_OFFSET)),
+                        );
+                    }
+                    on:mouseleave=move |_| set_tooltip.set(None)
+                />
+            }
+                .into_any()
+        } else {
+            view! {
+                <canvas
+                    id=canvas_id
+                    class=canvas_class
+                    style=canvas_style
+                />
+            }
+                .into_any()
+        }}
+        {move || {
+            tooltip
+                .get()
+                .map(|(text, left, top)| {
+                    view! {
+                        <div
+                            class="eld-tooltip"
+                            style=format!("{TOOLTIP_STYLE} left: {left}px; top: {top}px;")
+                        >
+                            {text}
+                        </div>
+                    }
+                })
+        }}
+    };
+
+    let class = crate::chart::container_class(container_class);
+    // Only attach a scroll listener when an `on_scroll` handler is given.
+    match on_scroll {
+        Some(on_scroll) => view! {
+            <div
+                id=format!("{canvas_id}-container")
+                class=class
+                style=container_style
+                on:scroll=move |_| on_scroll.run(container_scroll_left(canvas_id))
+            >
+                {chart}
+            </div>
+        }
+        .into_any(),
+        None => view! {
+            <div id=format!("{canvas_id}-container") class=class style=container_style>
+                {chart}
+            </div>
+        }
+        .into_any(),
+    }
+}
+
+/// Distance, in pixels, between the cursor and the tooltip's top-left corner.
+const TOOLTIP_OFFSET: f64 = 12.0;
+
+/// Inline style of the tooltip shown over the hovered segment; the position is appended.
+const TOOLTIP_STYLE: &str = "position: absolute; pointer-events: none; white-space: pre-line; \
+    background: #ffffff; border: 1px solid #cccccc; border-radius: 4px; padding: 4px 6px; \
+    font: 12px Arial;";
+
+/// Returns the width of the chart container, or `None` if it is not mounted.
+fn container_width(canvas_id: &str) -> Option<f64> {
+    web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.get_element_by_id(&format!("{canvas_id}-container")))
+        .map(|container| container.client_width() as f64)
+}
+
+/// Returns the current `scrollLeft` of the chart container, or `0.0` if it is not mounted.
+fn container_scroll_left(canvas_id: &str) -> f64 {
+    web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.get_element_by_id(&format!("{canvas_id}-container")))
+        .map_or(0.0, |container| container.scroll_left() as f64)
+}
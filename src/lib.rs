@@ -6,6 +6,10 @@
 #![doc = include_str!("../README.md")]
 
 pub mod chart;
+pub mod fmcsa;
+pub mod hos;
+pub mod share;
+pub mod svg;
 
 #[cfg(feature = "yew")]
 pub mod yew;
@@ -16,4 +20,12 @@ pub mod dioxus;
 #[cfg(feature = "lep")]
 pub mod leptos;
 
-pub use chart::{clear_chart, draw_chart, DutyStatus, Segment};
+#[cfg(feature = "ical")]
+pub mod ical;
+
+pub use chart::{
+    clear_chart, draw_availability_bars, draw_chart, draw_header, draw_hour_blocks, draw_team,
+    redraw_chart, reset_grid_cache, Align, Annotation, ChartError, ChartStyle, DutyStatus, GapKind,
+    GridMode, LogHeader, MergeStrategy, OverflowMode, Segment, SegmentBuilder, SegmentError,
+    SegmentErrorKind, SegmentStyle,
+};
@@ -0,0 +1,270 @@
+//! Encodes a driver's log into a compact string that fits in a URL fragment.
+//!
+//! The log is first written in a small binary form, then encoded with the URL-safe
+//! base64 alphabet (`-` and `_` instead of `+` and `/`) without padding, so the result
+//! can be appended after `#` as is:
+//!
+//! ```text
+//! <version: u8> then, per segment:
+//! <start minute: u16> <end minute: u16> <status: u8>
+//! <location length: u16> <location: UTF-8> <note length: u16> <note: UTF-8>
+//! <has odometer: u8> [<odometer: f32>]
+//! ```
+//!
+//! Integers and floats are big-endian, and hours are stored to the minute. Version `1`
+//! fragments, written before odometer readings were stored, are still decoded.
+
+use crate::chart::{DutyStatus, Segment};
+
+/// Version byte written at the start of the binary form.
+const FORMAT_VERSION: u8 = 2;
+
+/// The URL-safe base64 alphabet from RFC 4648, section 5.
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Encodes the log as a URL-safe fragment.
+///
+/// Segment hours are rounded to the nearest minute, and each location and note is
+/// truncated to 65535 bytes.
+///
+/// # Parameters
+/// - `segments`: A slice of `Segment` structs representing the driver's log.
+///
+/// # Returns
+/// - `String`: The fragment, made only of letters, digits, `-` and `_`.
+pub fn to_url_fragment(segments: &[Segment]) -> String {
+    encode_base64(&to_bytes(segments))
+}
+
+/// Decodes a fragment produced by `to_url_fragment` back into the log.
+///
+/// A leading `#` is ignored, so `location.hash` can be passed directly.
+///
+/// # Parameters
+/// - `fragment`: The encoded fragment.
+///
+/// # Returns
+/// - `Ok(Vec<Segment>)`: The decoded segments, in the order they were encoded.
+/// - `Err(String)`: If the fragment is not valid base64 or not a supported log encoding.
+pub fn from_url_fragment(fragment: &str) -> Result<Vec<Segment>, String> {
+    let bytes = decode_base64(fragment.strip_prefix('#').unwrap_or(fragment))?;
+    from_bytes(&bytes)
+}
+
+/// Writes the log in the compact binary form described in the module docs.
+fn to_bytes(segments: &[Segment]) -> Vec<u8> {
+    let mut bytes = vec![FORMAT_VERSION];
+    for segment in segments {
+        bytes.extend(hour_to_minutes(segment.start_hour).to_be_bytes());
+        bytes.extend(hour_to_minutes(segment.end_hour).to_be_bytes());
+        bytes.push(segment.status.index() as u8);
+        write_text(&mut bytes, &segment.location);
+        write_text(&mut bytes, &segment.note);
+        match segment.odometer {
+            Some(reading) => {
+                bytes.push(1);
+                bytes.extend(reading.to_be_bytes());
+            }
+            None => bytes.push(0),
+        }
+    }
+    bytes
+}
+
+/// Reads a log written by `to_bytes`.
+fn from_bytes(bytes: &[u8]) -> Result<Vec<Segment>, String> {
+    let (&version, mut rest) = bytes
+        .split_first()
+        .ok_or_else(|| "Fragment is empty".to_string())?;
+    if !(1..=FORMAT_VERSION).contains(&version) {
+        return Err(format!("Unsupported fragment version: {version}"));
+    }
+
+    let mut segments = Vec::new();
+    while !rest.is_empty() {
+        let start = read_u16(&mut rest)?;
+        let end = read_u16(&mut rest)?;
+        let status = take(&mut rest, 1)?[0];
+        let status = DutyStatus::ALL
+            .get(status as usize)
+            .cloned()
+            .ok_or_else(|| format!("Unknown duty status code: {status}"))?;
+        let location = read_text(&mut rest)?;
+        let note = read_text(&mut rest)?;
+        let odometer = if version >= 2 && take(&mut rest, 1)?[0] != 0 {
+            let bytes = take(&mut rest, 4)?;
+            Some(f32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+        } else {
+            None
+        };
+        segments.push(Segment {
+            start_hour: start as f32 / 60.0,
+            end_hour: end as f32 / 60.0,
+            status,
+            location,
+            note,
+            odometer,
+        });
+    }
+    Ok(segments)
+}
+
+/// Converts an hour of the day to whole minutes, clamped to the `u16` range.
+fn hour_to_minutes(hour: f32) -> u16 {
+    (hour * 60.0).round().clamp(0.0, u16::MAX as f32) as u16
+}
+
+/// Appends a length-prefixed UTF-8 string, truncated to a character boundary if too long.
+fn write_text(bytes: &mut Vec<u8>, text: &str) {
+    let mut len = text.len().min(u16::MAX as usize);
+    while !text.is_char_boundary(len) {
+        len -= 1;
+    }
+    bytes.extend((len as u16).to_be_bytes());
+    bytes.extend(&text.as_bytes()[..len]);
+}
+
+/// Reads a length-prefixed UTF-8 string.
+fn read_text(rest: &mut &[u8]) -> Result<String, String> {
+    let len = read_u16(rest)? as usize;
+    String::from_utf8(take(rest, len)?.to_vec())
+        .map_err(|_| "Fragment contains invalid UTF-8 text".to_string())
+}
+
+/// Reads a big-endian `u16`.
+fn read_u16(rest: &mut &[u8]) -> Result<u16, String> {
+    let bytes = take(rest, 2)?;
+    Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+}
+
+/// Splits `len` bytes off the front of `rest`.
+fn take<'a>(rest: &mut &'a [u8], len: usize) -> Result<&'a [u8], String> {
+    if rest.len() < len {
+        return Err("Fragment ends in the middle of a segment".to_string());
+    }
+    let (head, tail) = rest.split_at(len);
+    *rest = tail;
+    Ok(head)
+}
+
+/// Encodes bytes as unpadded URL-safe base64.
+fn encode_base64(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let group = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        for i in 0..=chunk.len() {
+            encoded.push(ALPHABET[(group >> (18 - 6 * i) & 0x3f) as usize] as char);
+        }
+    }
+    encoded
+}
+
+/// Decodes unpadded URL-safe base64; trailing `=` padding is tolerated.
+fn decode_base64(text: &str) -> Result<Vec<u8>, String> {
+    let text = text.trim_end_matches('=');
+    if text.len() % 4 == 1 {
+        return Err("Fragment has an invalid base64 length".to_string());
+    }
+    let mut bytes = Vec::with_capacity(text.len() * 3 / 4);
+    for chunk in text.as_bytes().chunks(4) {
+        let mut group = 0u32;
+        for (i, &c) in chunk.iter().enumerate() {
+            let value = ALPHABET
+                .iter()
+                .position(|&a| a == c)
+                .ok_or_else(|| format!("Invalid base64 character in fragment: {}", c as char))?;
+            group |= (value as u32) << (18 - 6 * i);
+        }
+        bytes.extend(&group.to_be_bytes()[1..chunk.len()]);
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_log() -> Vec<Segment> {
+        vec![
+            Segment::builder(0.0, 6.0, DutyStatus::OffDuty)
+                .location("Dallas, TX")
+                .build(),
+            Segment::builder(6.0, 6.5, DutyStatus::OnDuty)
+                .note("Pre-trip inspection ✓")
+                .build(),
+            Segment::builder(6.5, 11.25, DutyStatus::Driving)
+                .odometer(123_456.5)
+                .build(),
+            Segment::builder(11.25, 24.0, DutyStatus::PersonalConveyance).build(),
+        ]
+    }
+
+    #[test]
+    fn round_trips_a_log() {
+        let log = sample_log();
+        assert_eq!(from_url_fragment(&to_url_fragment(&log)), Ok(log));
+    }
+
+    #[test]
+    fn round_trips_an_empty_log() {
+        assert_eq!(from_url_fragment(&to_url_fragment(&[])), Ok(Vec::new()));
+    }
+
+    #[test]
+    fn accepts_a_leading_hash() {
+        let log = sample_log();
+        let fragment = format!("#{}", to_url_fragment(&log));
+        assert_eq!(from_url_fragment(&fragment), Ok(log));
+    }
+
+    #[test]
+    fn fragment_is_url_safe() {
+        let fragment = to_url_fragment(&sample_log());
+        assert!(!fragment.contains(['+', '/', '=']));
+        assert!(fragment
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+    }
+
+    #[test]
+    fn encodes_the_last_two_alphabet_characters_url_safely() {
+        // Standard base64 encodes these bytes as "+/+/".
+        assert_eq!(encode_base64(&[0xfb, 0xff, 0xbf]), "-_-_");
+        assert_eq!(decode_base64("-_-_"), Ok(vec![0xfb, 0xff, 0xbf]));
+        assert_eq!(encode_base64(b"Man"), "TWFu");
+        assert_eq!(encode_base64(b"Ma"), "TWE");
+        assert_eq!(decode_base64("TWE="), Ok(b"Ma".to_vec()));
+    }
+
+    #[test]
+    fn decodes_a_version_1_fragment_without_an_odometer() {
+        let mut bytes = vec![1];
+        bytes.extend(360_u16.to_be_bytes());
+        bytes.extend(660_u16.to_be_bytes());
+        bytes.push(DutyStatus::Driving.index() as u8);
+        bytes.extend([0, 0, 0, 0]);
+
+        assert_eq!(
+            from_url_fragment(&encode_base64(&bytes)),
+            Ok(vec![
+                Segment::builder(6.0, 11.0, DutyStatus::Driving).build()
+            ])
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_fragments() {
+        assert!(from_url_fragment("").is_err());
+        assert!(from_url_fragment("A").is_err());
+        assert!(from_url_fragment("AB+/").is_err());
+        assert!(from_url_fragment(&encode_base64(&[9])).is_err());
+
+        let fragment = to_url_fragment(&sample_log());
+        assert!(from_url_fragment(&fragment[..fragment.len() - 4]).is_err());
+    }
+}
@@ -0,0 +1,510 @@
+//! Renders the ELD chart as a standalone SVG document.
+//!
+//! Unlike `draw_chart`, the SVG renderer needs no browser or canvas, so it can be used
+//! server-side to produce reports, or embedded directly into HTML pages.
+
+use crate::chart::{
+    align_offset, axis_range, clip_to_axis, content_width, display_row, fill_end_of_day,
+    fit_to_span, generate_hour_labels, grid_right, hour_label_font_px, hour_label_offset,
+    hour_to_x, is_sub_status, legend_entries, odometer_readings, row_label, row_y, segment_x_range,
+    status_label, status_steps, status_totals, status_y, vertical_grid_hours, ChartProps,
+    ChartStyle, DutyStatus, Segment, SegmentStyle, NOW_LABEL_OFFSET, ODOMETER_TEXT_OFFSET,
+    ODOMETER_TICK_LENGTH, PADDING_X, SIDE_PANEL_LINE_HEIGHT,
+};
+use crate::hos::driving_limit_reached_at;
+use std::fmt::Write;
+
+/// Options controlling the SVG output.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SvgOptions {
+    /// Whether segments are tagged with CSS classes instead of inline colors.
+    ///
+    /// When `true`, each segment carries a class such as `status-driving` and no
+    /// stroke or fill color, so a stylesheet can recolor the chart.
+    pub use_css_classes: bool,
+
+    /// The output resolution, in dots per inch.
+    ///
+    /// The SVG's `width` and `height` are scaled by `dpi / 96.0` while the `viewBox`
+    /// keeps the logical size, so every coordinate and font grows in proportion.
+    /// Use `300.0` for crisp print exports. Defaults to `96.0` (CSS pixels).
+    pub dpi: f32,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        Self {
+            use_css_classes: false,
+            dpi: 96.0,
+        }
+    }
+}
+
+/// Renders the chart as an SVG document.
+///
+/// The layout matches the canvas produced by `draw_chart`: status rows with their
+/// labels, hour labels, vertical lines per `grid_mode`, and the duty status segments.
+/// Segments running past `hours_span` are handled per `on_overflow`.
+///
+/// Segments can come from any iterator, such as a database cursor, so callers do not
+/// need to collect a day into a `Vec` first; pass `log.iter().cloned()` for a slice.
+/// The renderer buffers the one day it draws.
+///
+/// # Parameters
+/// - `segments`: The segments of the driver's log, in any order.
+/// - `props`: The chart properties; `width` and `height` set the SVG size at 96 DPI.
+/// - `options`: The SVG-specific output options.
+///
+/// # Returns
+/// - `String`: The SVG markup.
+pub fn render_svg(
+    segments: impl IntoIterator<Item = Segment>,
+    props: &ChartProps,
+    options: &SvgOptions,
+) -> String {
+    let segments: Vec<Segment> = segments.into_iter().collect();
+    let (width, height) = (props.width as f64, props.height as f64);
+    let scale = options.dpi as f64 / 96.0;
+
+    let mut svg = String::new();
+    let _ = write!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="0 0 {width} {height}">"#,
+        width * scale,
+        height * scale
+    );
+    write_day(&mut svg, &segments, props, options);
+    svg.push_str("</svg>");
+    svg
+}
+
+/// Height in pixels of the header drawn above each day by `render_week_svg`.
+const DAY_HEADER_HEIGHT: f64 = 30.0;
+
+/// Renders several days as one SVG document, one chart stacked above the next.
+///
+/// Each day is drawn in its own `<g class="day">` group under a "Day N" header, and
+/// carries page-break hints so that converting the SVG to PDF server-side puts every
+/// day on its own page.
+///
+/// # Parameters
+/// - `days`: The segments of each day, in chronological order.
+/// - `props`: The chart properties shared by every day; `width` and `height` size one day.
+///
+/// # Returns
+/// - `String`: The SVG markup.
+pub fn render_week_svg(days: &[Vec<Segment>], props: &ChartProps) -> String {
+    let width = props.width as f64;
+    let day_height = props.height as f64 + DAY_HEADER_HEIGHT;
+    let height = day_height * days.len() as f64;
+
+    let mut svg = String::new();
+    let _ = write!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+    );
+    for (i, day) in days.iter().enumerate() {
+        let _ = write!(
+            svg,
+            r#"<g class="day" transform="translate(0, {})" style="break-after: page; page-break-after: always">"#,
+            day_height * i as f64
+        );
+        let _ = write!(
+            svg,
+            r#"<text x="10" y="{}" fill="{}" style="font: {}">Day {}</text>"#,
+            DAY_HEADER_HEIGHT - 10.0,
+            props.label_color,
+            props.font,
+            i + 1
+        );
+        let _ = write!(svg, r#"<g transform="translate(0, {DAY_HEADER_HEIGHT})">"#);
+        write_day(&mut svg, day, props, &SvgOptions::default());
+        svg.push_str("</g></g>");
+    }
+    svg.push_str("</svg>");
+    svg
+}
+
+/// Writes the background, grid and segments of one day into `svg`.
+fn write_day(svg: &mut String, segments: &[Segment], props: &ChartProps, options: &SvgOptions) {
+    let (segments, hours_span) = fit_to_span(&fill_end_of_day(segments, props), props);
+    let segments = &segments;
+    let props = &ChartProps {
+        hours_span,
+        ..props.clone()
+    };
+    let height = props.height as f64;
+    let width = content_width(props.width as f64, props);
+    let top = row_y(0, height, props);
+
+    let _ = write!(
+        svg,
+        r#"<rect width="{}" height="{height}" fill="{}"/>"#,
+        props.width, props.background_color
+    );
+    let offset = align_offset(props.width as f64, props);
+    let _ = write!(svg, r#"<g transform="translate({offset}, 0)">"#);
+
+    let _ = write!(
+        svg,
+        r#"<g class="grid" stroke="{}" fill="{}" style="font: {}">"#,
+        props.grid_color, props.label_color, props.font
+    );
+    for i in 0..=4 {
+        let y = row_y(i, height, props);
+        let _ = write!(
+            svg,
+            r#"<line x1="{PADDING_X}" y1="{y}" x2="{}" y2="{y}"/>"#,
+            grid_right(width, props)
+        );
+        if i < 4 {
+            let _ = write!(
+                svg,
+                r#"<text x="10" y="{}" stroke="none">{}</text>"#,
+                (y + row_y(i + 1, height, props)) / 2.0,
+                row_label(display_row(i, props), props)
+            );
+        }
+    }
+
+    let (axis_start, axis_end) = axis_range(props);
+    let hour_labels = generate_hour_labels(props);
+    for hour in vertical_grid_hours(segments, props) {
+        let x = hour_to_x(hour, width, props);
+        let _ = write!(svg, r#"<line x1="{x}" y1="{top}" x2="{x}" y2="{height}"/>"#);
+    }
+
+    let label_px = hour_label_font_px(width, props);
+    for (hour, label) in hour_labels.iter().filter(|(hour, _)| hour % 2 == 0) {
+        let _ = write!(
+            svg,
+            r#"<text x="{}" y="{}" stroke="none" style="font: {label_px}px Arial">{label}</text>"#,
+            hour_to_x(*hour as f32, width, props) - hour_label_offset(label_px),
+            height - 10.0
+        );
+    }
+    svg.push_str("</g>");
+
+    svg.push_str(r#"<g class="segments">"#);
+    let overage_start = props
+        .highlight_overage
+        .then(|| driving_limit_reached_at(segments))
+        .flatten();
+    if props.style == ChartStyle::GridLines && props.segment_style == SegmentStyle::Line {
+        for (from, to) in status_steps(segments) {
+            if !(axis_start..=axis_end).contains(&to.start_hour) {
+                continue;
+            }
+            let x = hour_to_x(to.start_hour, width, props);
+            let paint = if options.use_css_classes {
+                format!(r#"class="{}""#, status_class(&to.status))
+            } else {
+                format!(r#"stroke="{}""#, props.color_for(&to.status))
+            };
+            let _ = write!(
+                svg,
+                r#"<line x1="{x}" y1="{}" x2="{x}" y2="{}" stroke-width="4" {paint}/>"#,
+                status_y(&from.status, height, props),
+                status_y(&to.status, height, props)
+            );
+        }
+    }
+    for segment in segments {
+        let Some(segment) = clip_to_axis(segment, props) else {
+            continue;
+        };
+        let segment = &segment;
+        let y = status_y(&segment.status, height, props);
+        let (x_start, x_end) = segment_x_range(segment, width, props);
+        let paint = if options.use_css_classes {
+            format!(r#"class="{}""#, status_class(&segment.status))
+        } else {
+            color_paint(props.color_for(&segment.status), props)
+        };
+        let paint = if is_sub_status(&segment.status) {
+            format!(r#"{paint} stroke-dasharray="6 4""#)
+        } else {
+            paint
+        };
+        write_segment_shape(svg, x_start, x_end, y, &paint, height, props);
+
+        if let Some(limit_hour) = overage_start {
+            if segment.status == DutyStatus::Driving && segment.end_hour > limit_hour {
+                let x_over = hour_to_x(segment.start_hour.max(limit_hour), width, props);
+                let paint = if options.use_css_classes {
+                    r#"class="status-overage""#.to_string()
+                } else {
+                    color_paint(&props.overage_color, props)
+                };
+                write_segment_shape(svg, x_over, x_end, y, &paint, height, props);
+            }
+        }
+    }
+    svg.push_str("</g>");
+
+    if let Some(hour) = props
+        .now_hour
+        .filter(|hour| (axis_start..=axis_end).contains(hour))
+    {
+        let x = hour_to_x(hour, width, props);
+        let _ = write!(
+            svg,
+            r#"<g class="now" stroke="{color}" fill="{color}"><line x1="{x}" y1="{top}" x2="{x}" y2="{}" stroke-width="2"/><text x="{x}" y="{}" stroke="none" style="font: bold 10px Arial" text-anchor="middle">NOW</text></g>"#,
+            row_y(4, height, props),
+            top - NOW_LABEL_OFFSET,
+            color = props.now_color
+        );
+    }
+
+    if props.show_odometer {
+        let bottom = row_y(4, height, props);
+        let _ = write!(
+            svg,
+            r#"<g class="odometer" stroke="{}" fill="{}" style="font: 10px Arial" text-anchor="middle">"#,
+            props.grid_color, props.label_color
+        );
+        for (hour, reading) in odometer_readings(segments, props) {
+            let x = hour_to_x(hour, width, props);
+            let _ = write!(
+                svg,
+                r#"<line x1="{x}" y1="{bottom}" x2="{x}" y2="{}"/><text x="{x}" y="{}" stroke="none">{reading}</text>"#,
+                bottom + ODOMETER_TICK_LENGTH,
+                bottom + ODOMETER_TEXT_OFFSET
+            );
+        }
+        svg.push_str("</g>");
+    }
+
+    if props.side_panel {
+        let left = grid_right(width, props);
+        let totals = status_totals(segments);
+        let _ = write!(
+            svg,
+            r#"<g class="side-panel" fill="{}" style="font: 12px Arial">"#,
+            props.label_color
+        );
+        for (i, (status, color)) in legend_entries(segments, props).into_iter().enumerate() {
+            let y = top + i as f64 * SIDE_PANEL_LINE_HEIGHT;
+            let _ = write!(
+                svg,
+                r#"<rect x="{}" y="{}" width="10" height="10" fill="{color}"/><text x="{}" y="{y}">{}</text><text x="{}" y="{y}" text-anchor="end">{:.1}h</text>"#,
+                left + 10.0,
+                y - 10.0,
+                left + 26.0,
+                status_label(&status),
+                width - 10.0,
+                totals[status.index()]
+            );
+        }
+        svg.push_str("</g>");
+    }
+    svg.push_str("</g>");
+}
+
+/// Returns the attribute painting a segment shape in `color`: a stroke for lines, a fill otherwise.
+fn color_paint(color: &str, props: &ChartProps) -> String {
+    match (props.style, props.segment_style) {
+        (ChartStyle::GridLines, SegmentStyle::Line) => format!(r#"stroke="{color}""#),
+        _ => format!(r#"fill="{color}""#),
+    }
+}
+
+/// Writes one segment's shape between `x_start` and `x_end` according to the chart style.
+fn write_segment_shape(
+    svg: &mut String,
+    x_start: f64,
+    x_end: f64,
+    y: f64,
+    paint: &str,
+    height: f64,
+    props: &ChartProps,
+) {
+    let (top, bottom) = (row_y(0, height, props), row_y(4, height, props));
+    let filled = (props.style, props.segment_style) != (ChartStyle::GridLines, SegmentStyle::Line);
+    let paint = match &props.segment_border {
+        Some((color, line_width)) if filled => {
+            format!(r#"{paint} stroke="{color}" stroke-width="{line_width}""#)
+        }
+        _ => paint.to_string(),
+    };
+    let _ = match (props.style, props.segment_style) {
+        (ChartStyle::GridLines, SegmentStyle::Line) => write!(
+            svg,
+            r#"<line x1="{x_start}" y1="{y}" x2="{x_end}" y2="{y}" stroke-width="4" {paint}/>"#
+        ),
+        (ChartStyle::GridLines, SegmentStyle::Pill) => write!(
+            svg,
+            r#"<rect x="{x_start}" y="{}" width="{}" height="{}" rx="{}" {paint}/>"#,
+            y - props.pill_height / 2.0,
+            x_end - x_start,
+            props.pill_height,
+            props.pill_height / 2.0
+        ),
+        (ChartStyle::Ribbon, _) => write!(
+            svg,
+            r#"<rect x="{x_start}" y="{top}" width="{}" height="{}" {paint}/>"#,
+            x_end - x_start,
+            bottom - top
+        ),
+    };
+}
+
+/// Returns the CSS class used for a duty status, e.g. `status-driving`.
+fn status_class(status: &DutyStatus) -> &'static str {
+    match status {
+        DutyStatus::OffDuty => "status-off-duty",
+        DutyStatus::Sleeper => "status-sleeper",
+        DutyStatus::Driving => "status-driving",
+        DutyStatus::OnDuty => "status-on-duty",
+        DutyStatus::PersonalConveyance => "status-personal-conveyance",
+        DutyStatus::YardMove => "status-yard-move",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chart::{segment_rects, DrawCommand};
+
+    fn segment(start_hour: f32, end_hour: f32, status: DutyStatus) -> Segment {
+        Segment::builder(start_hour, end_hour, status).build()
+    }
+
+    fn sample_log() -> Vec<Segment> {
+        vec![
+            segment(0.0, 6.0, DutyStatus::OffDuty),
+            segment(6.0, 10.0, DutyStatus::Driving),
+            segment(10.0, 24.0, DutyStatus::OnDuty),
+        ]
+    }
+
+    #[test]
+    fn css_classes_tag_driving_segments_where_segment_rects_puts_them() {
+        let log = sample_log();
+        // A 720-pixel grid gives every hour 30 pixels.
+        let props = ChartProps {
+            width: 860,
+            ..ChartProps::default()
+        };
+        let options = SvgOptions {
+            use_css_classes: true,
+            ..SvgOptions::default()
+        };
+        let svg = render_svg(log.iter().cloned(), &props, &options);
+
+        let driving = segment_rects(&log, &props)
+            .into_iter()
+            .find_map(|command| match command {
+                DrawCommand::Line { x1, y1, x2, y2, .. }
+                    if y1 == y2 && y1 == status_y(&DutyStatus::Driving, 300.0, &props) =>
+                {
+                    Some((x1, y1, x2, y2))
+                }
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(driving, (250.0, 177.5, 370.0, 177.5));
+        let (x1, y1, x2, y2) = driving;
+        assert!(svg.contains(&format!(
+            r#"<line x1="{x1}" y1="{y1}" x2="{x2}" y2="{y2}" stroke-width="4" class="status-driving"/>"#
+        )));
+        assert!(!svg.contains(r#"stroke="green""#));
+    }
+
+    #[test]
+    fn iterator_and_slice_input_render_the_same_svg() {
+        let log = sample_log();
+        let props = ChartProps::default();
+        let options = SvgOptions::default();
+
+        let from_slice = render_svg(log.iter().cloned(), &props, &options);
+        let streamed = (0..3).map(|i| match i {
+            0 => segment(0.0, 6.0, DutyStatus::OffDuty),
+            1 => segment(6.0, 10.0, DutyStatus::Driving),
+            _ => segment(10.0, 24.0, DutyStatus::OnDuty),
+        });
+        assert_eq!(render_svg(streamed, &props, &options), from_slice);
+        assert_eq!(render_svg(log, &props, &options), from_slice);
+    }
+
+    #[test]
+    fn week_svg_stacks_one_grid_per_day() {
+        let days = vec![sample_log(), sample_log(), Vec::new()];
+        let svg = render_week_svg(&days, &ChartProps::default());
+
+        assert_eq!(svg.matches(r#"<g class="day""#).count(), 3);
+        assert_eq!(svg.matches(r#"<g class="grid""#).count(), 3);
+        assert!(svg.contains(">Day 3</text>"));
+        // Each 300-pixel day sits under a 30-pixel header.
+        assert!(svg.starts_with(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="900" height="990" viewBox="0 0 900 990">"#
+        ));
+        assert!(svg.contains(r#"transform="translate(0, 660)""#));
+    }
+
+    #[test]
+    fn dpi_scales_the_size_but_not_the_view_box() {
+        let log = sample_log();
+        let props = ChartProps::default();
+        let at_dpi = |dpi| {
+            render_svg(
+                log.iter().cloned(),
+                &props,
+                &SvgOptions {
+                    dpi,
+                    ..SvgOptions::default()
+                },
+            )
+        };
+
+        let screen = at_dpi(96.0);
+        let print = at_dpi(300.0);
+        assert!(screen.starts_with(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="900" height="300" viewBox="0 0 900 300">"#
+        ));
+        assert!(print.starts_with(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="2812.5" height="937.5" viewBox="0 0 900 300">"#
+        ));
+        let body = |svg: &str| svg.split_once('>').unwrap().1.to_string();
+        assert_eq!(body(&print), body(&screen));
+    }
+
+    #[test]
+    fn segment_border_outlines_each_pill() {
+        let log = sample_log();
+        let props = ChartProps {
+            width: 860,
+            segment_style: SegmentStyle::Pill,
+            segment_border: Some(("#000000".to_string(), 1.5)),
+            ..ChartProps::default()
+        };
+        let svg = render_svg(log.iter().cloned(), &props, &SvgOptions::default());
+
+        let pills = segment_rects(&log, &props);
+        assert_eq!(pills.len(), 3);
+        for pill in pills {
+            let DrawCommand::Pill {
+                x,
+                y,
+                width,
+                height,
+                color,
+                ..
+            } = pill
+            else {
+                panic!("expected a pill, got {pill:?}");
+            };
+            assert!(svg.contains(&format!(
+                r##"<rect x="{x}" y="{y}" width="{width}" height="{height}" rx="{}" fill="{color}" stroke="#000000" stroke-width="1.5"/>"##,
+                height / 2.0
+            )));
+        }
+
+        let lines = ChartProps {
+            segment_style: SegmentStyle::Line,
+            ..props
+        };
+        let svg = render_svg(log, &lines, &SvgOptions::default());
+        assert!(!svg.contains(r##"stroke="#000000""##));
+    }
+}